@@ -0,0 +1,228 @@
+//! Page aliases: redirect pages from old URLs to a page's current URL.
+
+use crate::config::UrlStyle;
+use crate::content::SiteManifest;
+use crate::error::{Error, Result};
+use crate::escape::html_escape;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A redirect from an old URL to a page.
+#[derive(Debug, PartialEq)]
+pub struct Redirect {
+    /// URL path of the alias as written (e.g., "/old/post.html", "/old/")
+    pub from: String,
+    /// Output path of the redirect page, relative to the output directory
+    pub output: PathBuf,
+    /// URL path of the target page (e.g., "/blog/post.html")
+    pub to: String,
+}
+
+/// Collect the redirects declared with `aliases` across the site.
+///
+/// Aliases ending in `.html` are written as-is; any other alias is treated as
+/// a directory and gets an `index.html`. Fails on malformed aliases, on two
+/// aliases sharing a path, and on aliases colliding with `outputs`, the paths
+/// of every real output relative to the output directory. Targets are links
+/// in the given URL `style`.
+pub fn collect_redirects(
+    manifest: &SiteManifest,
+    content_root: &Path,
+    outputs: &HashSet<PathBuf>,
+    style: UrlStyle,
+) -> Result<Vec<Redirect>> {
+    let mut redirects: Vec<Redirect> = Vec::new();
+
+    for content in manifest.content() {
+        for alias in &content.frontmatter.aliases {
+            let error = |message: String| Error::Alias {
+                path: content.source_path.clone(),
+                alias: alias.clone(),
+                message,
+            };
+
+            let output = alias_output(alias)
+                .ok_or_else(|| error("expected a site-relative path without '..'".to_string()))?;
+            if outputs.contains(&output) {
+                return Err(error(format!(
+                    "collides with generated page {}",
+                    output.display()
+                )));
+            }
+            if let Some(other) = redirects.iter().find(|r| r.output == output) {
+                return Err(error(format!("already used as an alias of {}", other.to)));
+            }
+
+            redirects.push(Redirect {
+                from: format!("/{}", alias.trim_start_matches('/')),
+                output,
+                to: content.url_path(content_root, style),
+            });
+        }
+    }
+
+    Ok(redirects)
+}
+
+/// Output path of an alias, or None if it is not a plain site-relative path.
+fn alias_output(alias: &str) -> Option<PathBuf> {
+    let trimmed = alias.trim_matches('/');
+    if trimmed.is_empty()
+        || alias.contains("://")
+        || alias.contains(['?', '#', '\\'])
+        || trimmed
+            .split('/')
+            .any(|seg| seg.is_empty() || seg == "." || seg == "..")
+    {
+        return None;
+    }
+
+    if trimmed.ends_with(".html") && !alias.ends_with('/') {
+        Some(PathBuf::from(trimmed))
+    } else {
+        Some(Path::new(trimmed).join("index.html"))
+    }
+}
+
+/// Build a redirect page pointing at `target_url`.
+///
+/// Uses a meta refresh plus a canonical link so search engines transfer the
+/// old URL to the new one; no JavaScript involved.
+pub fn redirect_html(target_url: &str) -> String {
+    let url = html_escape(target_url);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Redirecting to {url}</title>
+<link rel="canonical" href="{url}">
+<meta name="robots" content="noindex">
+<meta http-equiv="refresh" content="0; url={url}">
+</head>
+<body>
+<p>This page has moved to <a href="{url}">{url}</a>.</p>
+</body>
+</html>
+"#
+    )
+}
+
+/// Build a Netlify `_redirects` file with a permanent redirect per alias.
+///
+/// Both paths are prefixed with `base_path` (e.g., "/docs"), since the file
+/// matches paths from the root of the host.
+pub fn netlify_redirects(redirects: &[Redirect], base_path: &str) -> String {
+    redirects
+        .iter()
+        .map(|r| format!("{base_path}{} {base_path}{} 301\n", r.from, r.to))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_alias_output() {
+        assert_eq!(
+            alias_output("/old/post.html"),
+            Some(PathBuf::from("old/post.html"))
+        );
+        assert_eq!(
+            alias_output("/old/post/"),
+            Some(PathBuf::from("old/post/index.html"))
+        );
+        assert_eq!(
+            alias_output("old-post"),
+            Some(PathBuf::from("old-post/index.html"))
+        );
+        assert_eq!(alias_output("/"), None);
+        assert_eq!(alias_output("../etc/passwd"), None);
+        assert_eq!(alias_output("https://example.com/x"), None);
+        assert_eq!(alias_output("/a//b"), None);
+    }
+
+    #[test]
+    fn test_collect_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("_index.md"), "---\ntitle: Home\n---\n");
+        write(&root.join("blog/_index.md"), "---\ntitle: Blog\n---\n");
+        write(
+            &root.join("blog/post.md"),
+            "---\ntitle: Post\ndate: 2024-01-01\naliases: [/2024/post.html, /old/]\n---\n",
+        );
+
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Flat).unwrap();
+        let redirects =
+            collect_redirects(&manifest, root, &HashSet::new(), UrlStyle::Flat).unwrap();
+
+        assert_eq!(
+            redirects,
+            vec![
+                Redirect {
+                    from: "/2024/post.html".to_string(),
+                    output: PathBuf::from("2024/post.html"),
+                    to: "/blog/post.html".to_string(),
+                },
+                Redirect {
+                    from: "/old/".to_string(),
+                    output: PathBuf::from("old/index.html"),
+                    to: "/blog/post.html".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            netlify_redirects(&redirects, ""),
+            "/2024/post.html /blog/post.html 301\n/old/ /blog/post.html 301\n"
+        );
+        assert_eq!(
+            netlify_redirects(&redirects[..1], "/docs"),
+            "/docs/2024/post.html /docs/blog/post.html 301\n"
+        );
+    }
+
+    #[test]
+    fn test_collect_redirects_rejects_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("_index.md"), "---\ntitle: Home\n---\n");
+        write(
+            &root.join("about.md"),
+            "---\ntitle: About\naliases: [/contact.html]\n---\n",
+        );
+
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Flat).unwrap();
+        let outputs: HashSet<_> = [PathBuf::from("contact.html")].into();
+        let err = collect_redirects(&manifest, root, &outputs, UrlStyle::Flat).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("collides with generated page contact.html"));
+
+        // Two pages claiming the same alias
+        write(
+            &root.join("team.md"),
+            "---\ntitle: Team\naliases: /contact.html\n---\n",
+        );
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Flat).unwrap();
+        let err = collect_redirects(&manifest, root, &HashSet::new(), UrlStyle::Flat).unwrap_err();
+        assert!(err.to_string().contains("already used as an alias"));
+    }
+
+    #[test]
+    fn test_redirect_html() {
+        let html = redirect_html("https://example.com/a?x=1&y=2");
+        assert!(html.contains(r#"<link rel="canonical" href="https://example.com/a?x=1&amp;y=2">"#));
+        assert!(html.contains(
+            r#"<meta http-equiv="refresh" content="0; url=https://example.com/a?x=1&amp;y=2">"#
+        ));
+        assert!(!html.contains("<script"));
+    }
+}
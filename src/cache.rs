@@ -0,0 +1,501 @@
+//! Persistent build cache for incremental rebuilds.
+//!
+//! Records a fingerprint of every input that went into each output file.
+//! On the next build, outputs whose fingerprint is unchanged (and which
+//! still exist on disk) are skipped instead of re-rendered, and outputs the
+//! build no longer produces are deleted. Rendered summaries and feed bodies
+//! are kept alongside, so unchanged pages are not rendered for listings and
+//! feeds either.
+
+use crate::content::{Content, NavItem};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cache directory, relative to the config file location.
+pub const CACHE_DIR: &str = ".sukr-cache";
+
+/// File inside the cache directory holding output fingerprints.
+const CACHE_FILE: &str = "outputs";
+
+/// File inside the cache directory holding rendered HTML fragments.
+const FRAGMENTS_FILE: &str = "fragments";
+
+/// First line of the cache file. Bump when the format or hashing changes.
+const CACHE_HEADER: &str = "# sukr build cache v3";
+
+/// Stable 64-bit FNV-1a hasher.
+///
+/// `std`'s default hasher is not guaranteed to be stable across Rust
+/// releases, which would make a persistent cache useless.
+#[derive(Debug, Clone, Copy)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    /// Feed raw bytes into the hash.
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+        // Length suffix keeps ("ab", "c") distinct from ("a", "bc")
+        for byte in (bytes.len() as u64).to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+        self
+    }
+
+    /// Feed a string into the hash.
+    pub fn str(self, s: &str) -> Self {
+        self.bytes(s.as_bytes())
+    }
+
+    /// Feed the parts of a content item that affect its output into the
+    /// hash: kind, frontmatter, body, slug and permalink.
+    pub fn content(self, content: &Content) -> Self {
+        let frontmatter = serde_json::to_string(&(&content.kind, &content.frontmatter))
+            .expect("frontmatter serializes to JSON");
+        self.str(&frontmatter)
+            .str(&content.body)
+            .str(&content.slug)
+            .bytes(&[u8::from(content.permalink.is_some())])
+            .str(content.permalink.as_deref().unwrap_or_default())
+    }
+
+    /// Feed a previously computed fingerprint into the hash.
+    pub fn hash(self, other: u64) -> Self {
+        self.bytes(&other.to_le_bytes())
+    }
+
+    pub fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fingerprint the inputs shared by every output: the sukr version and the
+/// config file.
+pub fn config_fingerprint(config_path: &Path) -> Result<u64> {
+    let config = fs::read(config_path).map_err(|e| Error::ReadFile {
+        path: config_path.to_path_buf(),
+        source: e,
+    })?;
+
+    Ok(Fingerprint::new()
+        .str(env!("CARGO_PKG_VERSION"))
+        .bytes(&config)
+        .finish())
+}
+
+/// Fingerprint the inputs shared by every rendered page.
+///
+/// Covers the config (see [`config_fingerprint`]), every template file and
+/// the navigation tree. Since nav is embedded in every template, a change to
+/// any page's title, weight or nav label invalidates the whole site.
+pub fn site_fingerprint(config_hash: u64, template_dir: &Path, nav: &[NavItem]) -> Result<u64> {
+    let mut fp = Fingerprint::new()
+        .hash(config_hash)
+        .str(&serde_json::to_string(nav).expect("nav serializes to JSON"));
+
+    for entry in walkdir::WalkDir::new(template_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let contents = fs::read(entry.path()).map_err(|e| Error::ReadFile {
+            path: entry.path().to_path_buf(),
+            source: e,
+        })?;
+        fp = fp.str(&entry.path().to_string_lossy()).bytes(&contents);
+    }
+
+    Ok(fp.finish())
+}
+
+/// Output fingerprints from the previous build and the current one.
+///
+/// Outputs written on every build are recorded without a fingerprint, so
+/// they can still be removed once a build stops producing them.
+#[derive(Debug)]
+pub struct BuildCache {
+    dir: PathBuf,
+    previous: HashMap<PathBuf, Option<u64>>,
+    current: HashMap<PathBuf, Option<u64>>,
+}
+
+impl BuildCache {
+    /// Load the cache from `dir`. A missing or unreadable cache is empty.
+    pub fn load(dir: &Path) -> Self {
+        let previous = fs::read_to_string(dir.join(CACHE_FILE))
+            .ok()
+            .filter(|raw| raw.lines().next() == Some(CACHE_HEADER))
+            .map(|raw| parse_entries(&raw))
+            .unwrap_or_default();
+
+        Self {
+            dir: dir.to_path_buf(),
+            previous,
+            current: HashMap::new(),
+        }
+    }
+
+    /// Record the fingerprint for an output and report whether it must be rendered.
+    ///
+    /// Returns false only if the previous build produced the same fingerprint
+    /// for `relative` and the file still exists under `output_dir`.
+    pub fn needs_render(&mut self, output_dir: &Path, relative: &Path, fingerprint: u64) -> bool {
+        self.current
+            .insert(relative.to_path_buf(), Some(fingerprint));
+        self.previous.get(relative) != Some(&Some(fingerprint))
+            || !output_dir.join(relative).exists()
+    }
+
+    /// Record an output that is written on every build.
+    pub fn record(&mut self, relative: &Path) {
+        self.current.entry(relative.to_path_buf()).or_insert(None);
+    }
+
+    /// Delete outputs of the previous build that this build did not record,
+    /// such as removed or renamed pages and drafts from `serve --drafts`.
+    ///
+    /// Directories left empty are removed too. Returns the deleted files.
+    pub fn remove_orphans(&self, output_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut orphans: Vec<_> = self
+            .previous
+            .keys()
+            .filter(|relative| !self.current.contains_key(*relative))
+            .collect();
+        orphans.sort();
+
+        let mut removed = Vec::new();
+        for relative in orphans {
+            let path = output_dir.join(relative);
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(Error::RemoveFile { path, source: e }),
+            }
+            for dir in path.ancestors().skip(1) {
+                if dir == output_dir || fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+            removed.push(path);
+        }
+        Ok(removed)
+    }
+
+    /// Persist the outputs recorded during this build.
+    ///
+    /// Outputs not recorded this build are dropped from the cache.
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir).map_err(|e| Error::CreateDir {
+            path: self.dir.clone(),
+            source: e,
+        })?;
+
+        let mut entries: Vec<_> = self.current.iter().collect();
+        entries.sort();
+
+        let mut out = format!("{CACHE_HEADER}\n");
+        for (path, fingerprint) in entries {
+            match fingerprint {
+                Some(fingerprint) => {
+                    out.push_str(&format!("{:016x} {}\n", fingerprint, path.display()))
+                }
+                None => out.push_str(&format!("- {}\n", path.display())),
+            }
+        }
+
+        let path = self.dir.join(CACHE_FILE);
+        fs::write(&path, out).map_err(|e| Error::WriteFile { path, source: e })
+    }
+}
+
+/// Rendered HTML fragments of pages, such as summaries, by fingerprint.
+///
+/// A fragment's fingerprint covers the config, the page's source path,
+/// frontmatter and body, and its link targets, so it is reused until any of
+/// those change. Templates and navigation do not affect fragments.
+#[derive(Debug, Default)]
+pub struct FragmentCache {
+    dir: PathBuf,
+    config_hash: u64,
+    previous: HashMap<u64, Option<String>>,
+    current: HashMap<u64, Option<String>>,
+}
+
+impl FragmentCache {
+    /// Load the fragments from `dir`. A missing or unreadable cache is empty.
+    pub fn load(dir: &Path, config_hash: u64) -> Self {
+        let previous = fs::read_to_string(dir.join(FRAGMENTS_FILE))
+            .ok()
+            .and_then(|raw| {
+                let json = raw.strip_prefix(CACHE_HEADER)?.strip_prefix('\n')?;
+                serde_json::from_str::<HashMap<String, Option<String>>>(json).ok()
+            })
+            .map(|fragments| {
+                fragments
+                    .into_iter()
+                    .filter_map(|(hash, html)| Some((u64::from_str_radix(&hash, 16).ok()?, html)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            dir: dir.to_path_buf(),
+            config_hash,
+            previous,
+            current: HashMap::new(),
+        }
+    }
+
+    /// The `kind` fragment of `content` (e.g., its summary), rendered by
+    /// `render` only if neither this build nor the previous one has it.
+    ///
+    /// `dependencies` fingerprints the page's link targets.
+    pub fn get_or_render(
+        &mut self,
+        kind: &str,
+        content: &Content,
+        dependencies: u64,
+        render: impl FnOnce() -> Result<Option<String>>,
+    ) -> Result<Option<String>> {
+        let fingerprint = Fingerprint::new()
+            .hash(self.config_hash)
+            .str(kind)
+            .str(&content.source_path.to_string_lossy())
+            .content(content)
+            .hash(dependencies)
+            .finish();
+
+        if let Some(html) = self.current.get(&fingerprint) {
+            return Ok(html.clone());
+        }
+        let html = match self.previous.remove(&fingerprint) {
+            Some(html) => html,
+            None => render()?,
+        };
+        self.current.insert(fingerprint, html.clone());
+        Ok(html)
+    }
+
+    /// Persist the fragments used during this build.
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir).map_err(|e| Error::CreateDir {
+            path: self.dir.clone(),
+            source: e,
+        })?;
+
+        let fragments: std::collections::BTreeMap<_, _> = self
+            .current
+            .iter()
+            .map(|(hash, html)| (format!("{hash:016x}"), html))
+            .collect();
+        let json = serde_json::to_string(&fragments).expect("fragments serialize to JSON");
+
+        let path = self.dir.join(FRAGMENTS_FILE);
+        fs::write(&path, format!("{CACHE_HEADER}\n{json}"))
+            .map_err(|e| Error::WriteFile { path, source: e })
+    }
+}
+
+/// Parse `<hex fingerprint> <relative path>` lines, with `-` in place of
+/// the fingerprint for uncached outputs, skipping malformed ones.
+fn parse_entries(raw: &str) -> HashMap<PathBuf, Option<u64>> {
+    raw.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (hash, path) = line.split_once(' ')?;
+            let hash = match hash {
+                "-" => None,
+                hash => Some(u64::from_str_radix(hash, 16).ok()?),
+            };
+            Some((PathBuf::from(path), hash))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // Fixed value guards against accidental changes to the hash function
+        assert_eq!(Fingerprint::new().finish(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(
+            Fingerprint::new().str("a").finish(),
+            Fingerprint::new().str("a").finish()
+        );
+        assert_ne!(
+            Fingerprint::new().str("ab").str("c").finish(),
+            Fingerprint::new().str("a").str("bc").finish()
+        );
+    }
+
+    #[test]
+    fn test_content_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let load = |name: &str, raw: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, raw).unwrap();
+            Content::from_path(&path, crate::content::ContentKind::Page).unwrap()
+        };
+        let fingerprint = |content: &Content| Fingerprint::new().content(content).finish();
+
+        let page = load("a.md", "---\ntitle: A\n---\nBody.");
+        let mut moved = load("b.md", "---\ntitle: A\n---\nBody.");
+        moved.slug = page.slug.clone();
+        // Only what affects the output counts, not where the source lives
+        assert_eq!(fingerprint(&page), fingerprint(&moved));
+
+        let mut retitled = load("c.md", "---\ntitle: B\n---\nBody.");
+        retitled.slug = page.slug.clone();
+        let mut edited = page.clone();
+        edited.body.push_str(" More.");
+        let mut relinked = page.clone();
+        relinked.permalink = Some("/a/".to_string());
+        for changed in [&retitled, &edited, &relinked] {
+            assert_ne!(fingerprint(&page), fingerprint(changed));
+        }
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join(CACHE_DIR);
+        let output_dir = dir.path().join("public");
+        fs::create_dir_all(output_dir.join("blog")).unwrap();
+        fs::write(output_dir.join("blog/post.html"), "post").unwrap();
+
+        let relative = Path::new("blog/post.html");
+
+        // Empty cache: everything must render
+        let mut cache = BuildCache::load(&cache_dir);
+        assert!(cache.needs_render(&output_dir, relative, 42));
+        cache.save().unwrap();
+
+        // Same fingerprint and existing output: skip
+        let mut cache = BuildCache::load(&cache_dir);
+        assert!(!cache.needs_render(&output_dir, relative, 42));
+
+        // Changed fingerprint: render
+        let mut cache = BuildCache::load(&cache_dir);
+        assert!(cache.needs_render(&output_dir, relative, 43));
+    }
+
+    #[test]
+    fn test_cache_rerenders_missing_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join(CACHE_DIR);
+        let output_dir = dir.path().join("public");
+        let relative = Path::new("about.html");
+
+        let mut cache = BuildCache::load(&cache_dir);
+        cache.needs_render(&output_dir, relative, 7);
+        cache.save().unwrap();
+
+        // Output was never written, so it must be rendered again
+        let mut cache = BuildCache::load(&cache_dir);
+        assert!(cache.needs_render(&output_dir, relative, 7));
+    }
+
+    #[test]
+    fn test_cache_removes_orphaned_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join(CACHE_DIR);
+        let output_dir = dir.path().join("public");
+        fs::create_dir_all(output_dir.join("blog/old")).unwrap();
+        for file in ["index.html", "blog/old/index.html", "sitemap-2.xml"] {
+            fs::write(output_dir.join(file), "").unwrap();
+        }
+
+        let mut cache = BuildCache::load(&cache_dir);
+        cache.needs_render(&output_dir, Path::new("index.html"), 1);
+        cache.needs_render(&output_dir, Path::new("blog/old/index.html"), 2);
+        cache.record(Path::new("sitemap-2.xml"));
+        cache.save().unwrap();
+
+        // Next build only produces the homepage
+        let mut cache = BuildCache::load(&cache_dir);
+        cache.record(Path::new("index.html"));
+        let removed = cache.remove_orphans(&output_dir).unwrap();
+        assert_eq!(
+            removed,
+            [
+                output_dir.join("blog/old/index.html"),
+                output_dir.join("sitemap-2.xml")
+            ]
+        );
+        assert!(output_dir.join("index.html").exists());
+        assert!(!output_dir.join("blog").exists());
+
+        // Uncached outputs are never fresh
+        cache.save().unwrap();
+        let mut cache = BuildCache::load(&cache_dir);
+        assert!(cache.needs_render(&output_dir, Path::new("index.html"), 1));
+    }
+
+    #[test]
+    fn test_fragment_cache_reuses_unchanged_fragments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "---\ntitle: A\n---\nBody.").unwrap();
+        let post = Content::from_path(&path, crate::content::ContentKind::Post).unwrap();
+        let cache_dir = dir.path().join(CACHE_DIR);
+
+        let renders = std::cell::Cell::new(0);
+        let render = |fragments: &mut FragmentCache, kind: &str, dependencies: u64| {
+            fragments
+                .get_or_render(kind, &post, dependencies, || {
+                    renders.set(renders.get() + 1);
+                    Ok(Some(format!("<p>{kind}</p>")))
+                })
+                .unwrap()
+        };
+
+        let mut fragments = FragmentCache::load(&cache_dir, 1);
+        assert_eq!(
+            render(&mut fragments, "summary", 0).unwrap(),
+            "<p>summary</p>"
+        );
+        render(&mut fragments, "summary", 0);
+        fragments.save().unwrap();
+
+        // Rendered once per build, then reused by the next one
+        let mut fragments = FragmentCache::load(&cache_dir, 1);
+        render(&mut fragments, "summary", 0);
+        assert_eq!(renders.get(), 1);
+
+        // A new kind, link target or config renders again
+        render(&mut fragments, "content", 0);
+        render(&mut fragments, "summary", 7);
+        render(&mut FragmentCache::load(&cache_dir, 2), "summary", 0);
+        assert_eq!(renders.get(), 4);
+    }
+
+    #[test]
+    fn test_cache_ignores_unknown_format() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(CACHE_FILE), "0000000000000007 about.html\n").unwrap();
+
+        let cache = BuildCache::load(dir.path());
+        assert!(cache.previous.is_empty());
+    }
+}
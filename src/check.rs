@@ -0,0 +1,303 @@
+//! Post-build checker for internal links, assets and fragments.
+//!
+//! Scans every HTML file in the output directory and verifies that each
+//! internal `href`/`src` points at an existing output file and that each
+//! `#fragment` names an element `id` on the target page. External URLs are
+//! skipped; the check never touches the network.
+
+use crate::config::SiteConfig;
+use crate::escape::percent_decode;
+use crate::html::{self, HtmlScan};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A reference that does not resolve.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// Attribute name (e.g., "href")
+    pub attr: String,
+    /// Attribute value, with character references decoded
+    pub value: String,
+    /// 1-based line of the tag
+    pub line: usize,
+    /// Why the reference is broken
+    pub message: String,
+}
+
+/// Broken references of one output page.
+#[derive(Debug)]
+pub struct PageProblems {
+    /// Page path relative to the output directory
+    pub page: PathBuf,
+    pub problems: Vec<Problem>,
+}
+
+/// Check every HTML file under `output_dir`.
+///
+/// Absolute URLs under the site's `base_url` are checked like root-relative
+/// ones; other absolute URLs are external and skipped. Returns the pages with
+/// broken references, sorted by path.
+pub fn check_site(output_dir: &Path, config: &SiteConfig) -> std::io::Result<Vec<PageProblems>> {
+    let mut scans: HashMap<PathBuf, HtmlScan> = HashMap::new();
+    // Line of each reference, in scan order
+    let mut lines: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+    for entry in walkdir::WalkDir::new(output_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "html"))
+    {
+        let html = fs::read_to_string(entry.path())?;
+        let relative = entry
+            .path()
+            .strip_prefix(output_dir)
+            .unwrap_or(entry.path())
+            .to_path_buf();
+        let scan = html::scan(&html);
+        let reference_lines = scan
+            .references
+            .iter()
+            .map(|r| html::line_at(&html, r.offset))
+            .collect();
+        lines.insert(relative.clone(), reference_lines);
+        scans.insert(relative, scan);
+    }
+
+    let checker = Checker {
+        output_dir,
+        scans: &scans,
+        base_path: config.base_path(),
+        site_url: config.site_url(),
+    };
+
+    let mut pages: Vec<_> = scans.keys().collect();
+    pages.sort();
+
+    Ok(pages
+        .into_iter()
+        .filter_map(|page| {
+            let problems: Vec<_> = scans[page]
+                .references
+                .iter()
+                .zip(&lines[page])
+                .filter_map(|(reference, &line)| {
+                    checker
+                        .check(page, &reference.value)
+                        .err()
+                        .map(|message| Problem {
+                            attr: reference.attr.clone(),
+                            value: reference.value.clone(),
+                            line,
+                            message,
+                        })
+                })
+                .collect();
+            (!problems.is_empty()).then(|| PageProblems {
+                page: page.clone(),
+                problems,
+            })
+        })
+        .collect())
+}
+
+/// Format broken references grouped by page, with page paths under `output_dir`.
+pub fn format_report(output_dir: &Path, pages: &[PageProblems]) -> String {
+    let mut out = String::new();
+    for page in pages {
+        out.push_str(&format!("{}\n", output_dir.join(&page.page).display()));
+        for problem in &page.problems {
+            out.push_str(&format!(
+                "  line {}: {}=\"{}\": {}\n",
+                problem.line, problem.attr, problem.value, problem.message
+            ));
+        }
+    }
+    out
+}
+
+/// Resolves references against the scanned output.
+struct Checker<'a> {
+    output_dir: &'a Path,
+    scans: &'a HashMap<PathBuf, HtmlScan>,
+    base_path: String,
+    site_url: String,
+}
+
+impl Checker<'_> {
+    /// Check a reference found on `page`, returning why it is broken.
+    fn check(&self, page: &Path, value: &str) -> Result<(), String> {
+        let Some(url) = self.internal_url(value) else {
+            return Ok(());
+        };
+        let (url, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (url, None),
+        };
+        let url = url.split('?').next().unwrap_or(url);
+
+        // Empty path: a fragment on the same page
+        let target = if url.is_empty() {
+            page.to_path_buf()
+        } else {
+            self.resolve(page, url)?
+        };
+
+        match fragment {
+            Some(fragment) if !fragment.is_empty() => {
+                let Some(scan) = self.scans.get(&target) else {
+                    return Ok(()); // Fragments of non-HTML files are not checked
+                };
+                let fragment = percent_decode(fragment).unwrap_or_else(|| fragment.to_string());
+                if scan.ids.contains(&fragment) {
+                    Ok(())
+                } else {
+                    Err(format!("no element with id \"{fragment}\""))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The site-relative part of a URL, or None for external URLs.
+    fn internal_url<'v>(&self, value: &'v str) -> Option<&'v str> {
+        // Absolute URLs of this site are checked like root-relative ones
+        if !self.site_url.is_empty()
+            && let Some(rest) = value.strip_prefix(self.site_url.as_str())
+            && (rest.is_empty() || rest.starts_with(['/', '?', '#']))
+        {
+            let path = &value[self.site_url.len() - self.base_path.len()..];
+            return Some(if path.is_empty() { "/" } else { path });
+        }
+        (!html::is_external(value)).then_some(value)
+    }
+
+    /// Output file a URL path on `page` points at.
+    fn resolve(&self, page: &Path, url: &str) -> Result<PathBuf, String> {
+        let decoded = percent_decode(url).ok_or("malformed percent-encoding")?;
+
+        let (mut resolved, path) = match decoded.strip_prefix('/') {
+            Some(absolute) => {
+                let under_base = match self.base_path.as_str() {
+                    "" => Some(absolute),
+                    base => format!("/{absolute}")
+                        .strip_prefix(base)
+                        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+                        .map(|_| &absolute[base.len() - 1..]),
+                };
+                let path = under_base.ok_or("outside the base path")?;
+                (PathBuf::new(), path.trim_start_matches('/').to_string())
+            }
+            None => (
+                page.parent().unwrap_or(Path::new("")).to_path_buf(),
+                decoded.clone(),
+            ),
+        };
+
+        for component in Path::new(&path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        return Err("points outside the output directory".to_string());
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {}
+            }
+        }
+
+        let full = self.output_dir.join(&resolved);
+        if full.is_dir() {
+            resolved.push("index.html");
+            if !self.output_dir.join(&resolved).is_file() {
+                return Err("directory without index.html".to_string());
+            }
+        } else if decoded.ends_with('/') || !full.is_file() {
+            return Err("no such file".to_string());
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn config(base_url: &str) -> SiteConfig {
+        toml::from_str(&format!(
+            "title = \"Site\"\nauthor = \"Author\"\nbase_url = \"{base_url}\""
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_site() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path();
+        write(&out.join("style.css"), "");
+        write(
+            &out.join("index.html"),
+            "<link href=\"./style.css\"><a href=\"blog/\">Blog</a>\
+             <a href=\"https://example.org/blog/post.html#setup\">Post</a>\
+             <a href=\"https://elsewhere.com/missing.html\">External</a>\
+             <a href=\"mailto:me@example.org\">Mail</a>",
+        );
+        write(
+            &out.join("blog/index.html"),
+            "<a href=\"../index.html\">Home</a>",
+        );
+        write(
+            &out.join("blog/post.html"),
+            "<h2 id=\"setup\">Setup</h2>\
+             <a href=\"#setup\">Here</a>\
+             <a href=\"#nope\">Gone</a>\
+             <a href=\"/blog/index.html#missing\">Listing</a>\
+             <img src=\"../img/missing.png\">\
+             <a href=\"../../up.html\">Up</a>",
+        );
+
+        let pages = check_site(out, &config("https://example.org/")).unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].page, PathBuf::from("blog/post.html"));
+        let messages: Vec<_> = pages[0]
+            .problems
+            .iter()
+            .map(|p| (p.value.as_str(), p.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("#nope", "no element with id \"nope\""),
+                ("/blog/index.html#missing", "no element with id \"missing\""),
+                ("../img/missing.png", "no such file"),
+                ("../../up.html", "points outside the output directory"),
+            ]
+        );
+
+        let report = format_report(Path::new("public"), &pages);
+        assert!(report.starts_with("public/blog/post.html\n  line 1: href=\"#nope\": "));
+    }
+
+    #[test]
+    fn test_check_site_with_base_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path();
+        write(
+            &out.join("index.html"),
+            "<a href=\"/docs/about.html\">About</a><a href=\"/about.html\">Wrong</a>",
+        );
+        write(&out.join("about.html"), "");
+
+        let pages = check_site(out, &config("https://example.org/docs/")).unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].problems.len(), 1);
+        assert_eq!(pages[0].problems[0].message, "outside the base path");
+    }
+}
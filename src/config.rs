@@ -1,7 +1,8 @@
 //! Site configuration loading.
 
 use crate::error::{Error, Result};
-use serde::Deserialize;
+use crate::render::TocLevels;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -12,25 +13,238 @@ pub struct SiteConfig {
     pub title: String,
     /// Site author name.
     pub author: String,
-    /// Base URL for the site (used for feeds, canonical links).
+    /// Site description (used in feeds).
+    pub description: Option<String>,
+    /// Base URL for the site (used for feeds, canonical links). May include
+    /// a path for sites served from a subdirectory (e.g., "https://example.org/docs/").
     pub base_url: String,
+    /// Path the site is served under (e.g., "/docs"), overriding any path in
+    /// `base_url`.
+    pub base_path: Option<String>,
     /// Path configuration (all optional with defaults).
     #[serde(default)]
     pub paths: PathsConfig,
     /// Navigation configuration.
     #[serde(default)]
     pub nav: NavConfig,
+    /// Taxonomies to generate listing pages for (default: tags only).
+    #[serde(default = "default_taxonomies")]
+    pub taxonomies: Vec<TaxonomyConfig>,
+    /// Feed configuration.
+    #[serde(default)]
+    pub feed: FeedConfig,
+    /// robots.txt configuration (robots.txt is only generated when set).
+    pub robots: Option<RobotsConfig>,
+    /// Redirect configuration for page aliases.
+    #[serde(default)]
+    pub redirects: RedirectsConfig,
+    /// URL configuration.
+    #[serde(default)]
+    pub urls: UrlsConfig,
+    /// Domain rules for the external link report.
+    #[serde(default)]
+    pub external_links: ExternalLinksConfig,
 }
 
-/// Navigation configuration.
+/// URL configuration (`[urls]`).
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
+pub struct UrlsConfig {
+    /// How pages map to output files and links (default: flat).
+    pub style: UrlStyle,
+}
+
+/// How content pages map to output files and links.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UrlStyle {
+    /// `parent/slug.html`, linked as `/parent/slug.html`
+    #[default]
+    Flat,
+    /// `parent/slug/index.html`, linked as `/parent/slug/`
+    Pretty,
+}
+
+impl UrlStyle {
+    /// Link to an output path (e.g., "/blog/index.html").
+    ///
+    /// Pretty URLs drop the trailing `index.html` ("/blog/"); flat URLs link
+    /// to the file itself.
+    pub fn link(self, output_path: &str) -> String {
+        match self {
+            Self::Flat => output_path.to_string(),
+            Self::Pretty => output_path
+                .strip_suffix("index.html")
+                .unwrap_or(output_path)
+                .to_string(),
+        }
+    }
+}
+
+/// Redirect configuration (`[redirects]`).
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct RedirectsConfig {
+    /// Also write a Netlify `_redirects` file with a 301 per alias (default: false).
+    pub netlify: bool,
+}
+
+/// External link rules (`[external_links]`).
+///
+/// Domains match themselves and their subdomains ("example.com" covers
+/// "docs.example.com").
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct ExternalLinksConfig {
+    /// Domains links may point at; when set, any other domain is flagged.
+    pub allow: Vec<String>,
+    /// Domains links must not point at.
+    pub deny: Vec<String>,
+}
+
+/// robots.txt configuration (`[robots]`).
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct RobotsConfig {
+    /// User-agent groups (default: a single group for every crawler).
+    pub rules: Vec<RobotsRule>,
+}
+
+/// A robots.txt user-agent group (`[[robots.rules]]`).
+#[derive(Debug, Deserialize)]
+pub struct RobotsRule {
+    /// Crawler the group applies to (e.g., "*" or "GPTBot").
+    pub user_agent: String,
+    /// Path prefixes crawlers may fetch.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Path prefixes crawlers must not fetch.
+    #[serde(default)]
+    pub disallow: Vec<String>,
+}
+
+/// Feed configuration.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct FeedConfig {
+    /// Output formats for every feed (default: Atom only).
+    pub formats: Vec<FeedFormat>,
+    /// Maximum number of entries per feed (default: all).
+    pub limit: Option<usize>,
+    /// What each entry carries besides its summary (default: summary only).
+    pub content: FeedContent,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            formats: vec![FeedFormat::Atom],
+            limit: None,
+            content: FeedContent::default(),
+        }
+    }
+}
+
+/// Feed output format.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// Atom 1.0 (feed.xml)
+    Atom,
+    /// RSS 2.0 (rss.xml)
+    Rss,
+    /// JSON Feed 1.1 (feed.json)
+    Json,
+}
+
+impl FeedFormat {
+    /// Output file name of a feed in this format.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Atom => "feed.xml",
+            Self::Rss => "rss.xml",
+            Self::Json => "feed.json",
+        }
+    }
+
+    /// MIME type for `<link rel="alternate" type="...">`.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Atom => "application/atom+xml",
+            Self::Rss => "application/rss+xml",
+            Self::Json => "application/feed+json",
+        }
+    }
+}
+
+/// Entry content included in feeds.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedContent {
+    /// Summary only
+    #[default]
+    Summary,
+    /// Summary plus the full rendered HTML body
+    Full,
+}
+
+/// A taxonomy declared with `[[taxonomies]]`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaxonomyConfig {
+    /// Frontmatter key under `taxonomies:` and output directory name.
+    pub name: String,
+    /// Items per term listing page (default: no pagination).
+    #[serde(default)]
+    pub paginate_by: Option<usize>,
+    /// Generate feeds per term (default: true).
+    #[serde(default = "default_true")]
+    pub feed: bool,
+}
+
+impl TaxonomyConfig {
+    /// Default taxonomy settings for `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            paginate_by: None,
+            feed: true,
+        }
+    }
+}
+
+fn default_taxonomies() -> Vec<TaxonomyConfig> {
+    vec![TaxonomyConfig::new("tags")]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Navigation configuration.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
 pub struct NavConfig {
     /// Whether to display nested navigation (default: false).
     pub nested: bool,
     /// Enable table of contents (anchor nav) globally (default: false).
     /// Can be overridden per-page via frontmatter toc field.
     pub toc: bool,
+    /// Shallowest heading level in the table of contents (default: 2).
+    pub toc_min_level: u8,
+    /// Deepest heading level in the table of contents (default: 6).
+    pub toc_max_level: u8,
+}
+
+impl Default for NavConfig {
+    fn default() -> Self {
+        let levels = TocLevels::default();
+        Self {
+            nested: false,
+            toc: false,
+            toc_min_level: levels.min,
+            toc_max_level: levels.max,
+        }
+    }
 }
 
 /// Path configuration with sensible defaults.
@@ -67,10 +281,57 @@ impl SiteConfig {
             source: e,
         })?;
 
-        toml::from_str(&content).map_err(|e| Error::Config {
+        let config: Self = toml::from_str(&content).map_err(|e| Error::Config {
             path: path.to_path_buf(),
             message: e.to_string(),
-        })
+        })?;
+
+        let nav = &config.nav;
+        if !(1..=nav.toc_max_level).contains(&nav.toc_min_level) || nav.toc_max_level > 6 {
+            return Err(Error::Config {
+                path: path.to_path_buf(),
+                message: format!(
+                    "invalid toc levels {}-{} (expected 1 <= toc_min_level <= toc_max_level <= 6)",
+                    nav.toc_min_level, nav.toc_max_level
+                ),
+            });
+        }
+        Ok(config)
+    }
+
+    /// Path the site is served under, without a trailing slash ("" at the root).
+    ///
+    /// Taken from `base_path` when set, else from the path of `base_url`
+    /// ("https://example.org/docs/" → "/docs").
+    pub fn base_path(&self) -> String {
+        let path = match &self.base_path {
+            Some(path) => path.as_str(),
+            None => split_url(&self.base_url).1,
+        };
+        let trimmed = path.trim_matches('/');
+        if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("/{trimmed}")
+        }
+    }
+
+    /// Absolute URL of the site root, without a trailing slash
+    /// (e.g., "https://example.org/docs").
+    ///
+    /// Page URLs are this followed by their site-relative path.
+    pub fn site_url(&self) -> String {
+        format!("{}{}", split_url(&self.base_url).0, self.base_path())
+    }
+}
+
+/// Split a URL into its origin and path ("https://example.org/docs/" →
+/// ("https://example.org", "/docs/")).
+fn split_url(url: &str) -> (&str, &str) {
+    let host_start = url.find("://").map_or(0, |i| i + 3);
+    match url[host_start..].find('/') {
+        Some(i) => url.split_at(host_start + i),
+        None => (url, ""),
     }
 }
 
@@ -127,4 +388,171 @@ mod tests {
         assert_eq!(config.paths.static_dir, PathBuf::from("assets"));
         assert_eq!(config.paths.templates, PathBuf::from("theme"));
     }
+
+    #[test]
+    fn test_taxonomies_default_to_tags() {
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+        "#;
+
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.taxonomies.len(), 1);
+        assert_eq!(config.taxonomies[0].name, "tags");
+        assert!(config.taxonomies[0].feed);
+        assert_eq!(config.taxonomies[0].paginate_by, None);
+    }
+
+    #[test]
+    fn test_taxonomies_custom() {
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+
+            [[taxonomies]]
+            name = "categories"
+            paginate_by = 10
+
+            [[taxonomies]]
+            name = "authors"
+            feed = false
+        "#;
+
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        let names: Vec<_> = config.taxonomies.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["categories", "authors"]);
+        assert_eq!(config.taxonomies[0].paginate_by, Some(10));
+        assert!(config.taxonomies[0].feed);
+        assert!(!config.taxonomies[1].feed);
+    }
+
+    #[test]
+    fn test_feed_config() {
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+        "#;
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.feed.formats, vec![FeedFormat::Atom]);
+        assert_eq!(config.feed.limit, None);
+        assert_eq!(config.feed.content, FeedContent::Summary);
+
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+
+            [feed]
+            formats = ["rss", "json"]
+            limit = 20
+            content = "full"
+        "#;
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.feed.formats, vec![FeedFormat::Rss, FeedFormat::Json]);
+        assert_eq!(config.feed.limit, Some(20));
+        assert_eq!(config.feed.content, FeedContent::Full);
+    }
+
+    #[test]
+    fn test_toc_levels_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("site.toml");
+        let site = "title = \"Test\"\nauthor = \"Author\"\nbase_url = \"https://example.com\"\n";
+
+        fs::write(&path, site).unwrap();
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!((config.nav.toc_min_level, config.nav.toc_max_level), (2, 6));
+
+        fs::write(
+            &path,
+            format!("{site}[nav]\ntoc_min_level = 1\ntoc_max_level = 3\n"),
+        )
+        .unwrap();
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!((config.nav.toc_min_level, config.nav.toc_max_level), (1, 3));
+
+        fs::write(
+            &path,
+            format!("{site}[nav]\ntoc_min_level = 4\ntoc_max_level = 3\n"),
+        )
+        .unwrap();
+        let err = SiteConfig::load(&path).unwrap_err();
+        assert!(err.to_string().contains("invalid toc levels 4-3"));
+    }
+
+    #[test]
+    fn test_robots_config() {
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+        "#;
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        assert!(config.robots.is_none());
+
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+
+            [[robots.rules]]
+            user_agent = "*"
+            disallow = ["/drafts/"]
+
+            [[robots.rules]]
+            user_agent = "GPTBot"
+            disallow = ["/"]
+        "#;
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        let rules = &config.robots.unwrap().rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].user_agent, "*");
+        assert_eq!(rules[0].disallow, vec!["/drafts/"]);
+        assert!(rules[0].allow.is_empty());
+        assert_eq!(rules[1].user_agent, "GPTBot");
+    }
+
+    #[test]
+    fn test_base_path() {
+        let config = |extra: &str| -> SiteConfig {
+            toml::from_str(&format!("title = \"T\"\nauthor = \"A\"\n{extra}")).unwrap()
+        };
+
+        let root = config("base_url = \"https://example.org/\"");
+        assert_eq!(root.base_path(), "");
+        assert_eq!(root.site_url(), "https://example.org");
+
+        let subdir = config("base_url = \"https://example.org/docs/\"");
+        assert_eq!(subdir.base_path(), "/docs");
+        assert_eq!(subdir.site_url(), "https://example.org/docs");
+
+        let explicit = config("base_url = \"https://example.org\"\nbase_path = \"docs/v2/\"");
+        assert_eq!(explicit.base_path(), "/docs/v2");
+        assert_eq!(explicit.site_url(), "https://example.org/docs/v2");
+    }
+
+    #[test]
+    fn test_url_style() {
+        let toml = r#"
+            title = "Test"
+            author = "Author"
+            base_url = "https://example.com"
+
+            [urls]
+            style = "pretty"
+        "#;
+        let config: SiteConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.urls.style, UrlStyle::Pretty);
+
+        assert_eq!(UrlStyle::Pretty.link("/index.html"), "/");
+        assert_eq!(
+            UrlStyle::Pretty.link("/blog/post/index.html"),
+            "/blog/post/"
+        );
+        assert_eq!(UrlStyle::Pretty.link("/404.html"), "/404.html");
+        assert_eq!(UrlStyle::Flat.link("/blog/index.html"), "/blog/index.html");
+    }
 }
@@ -1,8 +1,14 @@
 //! Content discovery and frontmatter parsing.
 
+use crate::config::{FeedFormat, NavConfig, TaxonomyConfig, UrlStyle};
+use crate::date::{self, DateTime};
 use crate::error::{Error, Result};
-use gray_matter::{engine::YAML, Matter};
+use crate::pagination::{self, Pager};
+use crate::render::TocLevels;
+use crate::taxonomy::Taxonomy;
+use gray_matter::{engine::YAML, Matter, Pod};
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -12,8 +18,12 @@ pub(crate) const DEFAULT_WEIGHT: i64 = 50;
 /// High default weight for content that should appear last (e.g., projects).
 pub(crate) const DEFAULT_WEIGHT_HIGH: i64 = 99;
 
+/// Source file of the custom 404 page, relative to the content root.
+pub(crate) const NOT_FOUND_FILE: &str = "404.md";
+
 /// The type of content being processed.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ContentKind {
     /// Blog post with full metadata (date, tags, etc.)
     Post,
@@ -25,6 +35,95 @@ pub enum ContentKind {
     Project,
 }
 
+/// Field that section items are sorted by (`sort_by` in `_index.md`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    Date,
+    Weight,
+    Title,
+    Slug,
+}
+
+impl SortBy {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "date" => Some(Self::Date),
+            "weight" => Some(Self::Weight),
+            "title" => Some(Self::Title),
+            "slug" => Some(Self::Slug),
+            _ => None,
+        }
+    }
+
+    /// Order used when `sort_order` is not set: newest first for dates,
+    /// ascending otherwise.
+    fn default_order(self) -> SortOrder {
+        match self {
+            Self::Date => SortOrder::Desc,
+            _ => SortOrder::Asc,
+        }
+    }
+}
+
+/// Direction of a section sort (`sort_order` in `_index.md`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+
+/// How often a page is expected to change (`changefreq` in the sitemap).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(Self::Always),
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            "yearly" => Some(Self::Yearly),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Yearly => "yearly",
+            Self::Never => "never",
+        }
+    }
+}
+
 /// A navigation menu item discovered from the filesystem.
 #[derive(Debug, Clone, Serialize)]
 pub struct NavItem {
@@ -40,12 +139,18 @@ pub struct NavItem {
 }
 
 /// Parsed frontmatter from a content file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Frontmatter {
     pub title: String,
     pub description: Option<String>,
+    /// Publication date (`YYYY-MM-DD` or an RFC 3339 datetime)
     pub date: Option<String>,
+    /// Date of the last significant change (same formats as `date`)
+    pub updated: Option<String>,
+    /// Shorthand for the "tags" entry of `taxonomies`
     pub tags: Vec<String>,
+    /// Terms per taxonomy name (e.g., "categories" → ["rust"])
+    pub taxonomies: BTreeMap<String, Vec<String>>,
     /// Sort order for nav and listings
     pub weight: Option<i64>,
     /// For project cards: external link
@@ -58,6 +163,76 @@ pub struct Frontmatter {
     pub template: Option<String>,
     /// Enable table of contents (anchor nav in sidebar)
     pub toc: Option<bool>,
+    /// Shallowest heading level in the table of contents (overrides config)
+    pub toc_min_level: Option<u8>,
+    /// Deepest heading level in the table of contents (overrides config)
+    pub toc_max_level: Option<u8>,
+    /// Unfinished content, only built with --drafts
+    pub draft: bool,
+    /// Date from which the item is published (defaults to date)
+    pub publish_date: Option<String>,
+    /// For section indexes: items per listing page (None: no pagination)
+    pub paginate_by: Option<usize>,
+    /// For section indexes: field to sort items by
+    pub sort_by: Option<SortBy>,
+    /// For section indexes: sort direction (defaults per `sort_by`)
+    pub sort_order: Option<SortOrder>,
+    /// For section indexes: whether items are included in feeds
+    pub feed: Option<bool>,
+    /// For section indexes: whether the section gets its own feed
+    pub section_feed: bool,
+    /// Whether the page is listed in the sitemap (default: true)
+    pub sitemap: bool,
+    /// Ask search engines not to index the page (also leaves the sitemap)
+    pub noindex: bool,
+    /// Expected change frequency, for the sitemap
+    pub changefreq: Option<ChangeFreq>,
+    /// Sitemap priority relative to other pages (0.0 to 1.0)
+    pub priority: Option<f64>,
+    /// Old URL paths that redirect to this page
+    pub aliases: Vec<String>,
+    /// URL slug overriding the file name
+    pub slug: Option<String>,
+    /// For section indexes: URL pattern for items (e.g., "/blog/:year/:slug/")
+    pub permalink: Option<String>,
+}
+
+impl Frontmatter {
+    /// Whether the page belongs in the sitemap (`sitemap` unset or true,
+    /// and not `noindex`).
+    pub fn in_sitemap(&self) -> bool {
+        self.sitemap && !self.noindex
+    }
+
+    /// Heading levels in the table of contents, from frontmatter with
+    /// `[nav]` as the fallback.
+    pub fn toc_levels(&self, nav: &NavConfig) -> TocLevels {
+        TocLevels {
+            min: self.toc_min_level.unwrap_or(nav.toc_min_level),
+            max: self.toc_max_level.unwrap_or(nav.toc_max_level),
+        }
+    }
+
+    /// Parsed publication date.
+    pub fn date_time(&self) -> Option<DateTime> {
+        self.date.as_deref().and_then(DateTime::parse)
+    }
+
+    /// Parsed last-updated date, falling back to the publication date.
+    pub fn updated_time(&self) -> Option<DateTime> {
+        self.updated
+            .as_deref()
+            .and_then(DateTime::parse)
+            .or_else(|| self.date_time())
+    }
+
+    /// Terms assigned to this item in the named taxonomy.
+    pub fn terms(&self, taxonomy: &str) -> &[String] {
+        self.taxonomies
+            .get(taxonomy)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 /// A content item ready for rendering.
@@ -68,6 +243,8 @@ pub struct Content {
     pub body: String,
     pub source_path: PathBuf,
     pub slug: String,
+    /// URL path from the section's `permalink` pattern (e.g., "/blog/2024/post/")
+    pub permalink: Option<String>,
 }
 
 impl Content {
@@ -87,12 +264,15 @@ impl Content {
 
         let frontmatter = parse_frontmatter(path, &parsed)?;
 
-        // Derive slug from filename (without extension)
-        let slug = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("untitled")
-            .to_string();
+        // Slug from frontmatter, else the filename (without extension)
+        let slug = match &frontmatter.slug {
+            Some(slug) => slug.clone(),
+            None => path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("untitled")
+                .to_string(),
+        };
 
         Ok(Content {
             kind,
@@ -100,30 +280,61 @@ impl Content {
             body: parsed.content,
             source_path: path.to_path_buf(),
             slug,
+            permalink: None,
         })
     }
 
+    /// Whether this item is publicly visible: not a draft, and not
+    /// scheduled for a calendar date after `today`.
+    pub fn is_published(&self, today: DateTime) -> bool {
+        let fm = &self.frontmatter;
+        !fm.draft
+            && fm
+                .publish_date
+                .as_deref()
+                .or(fm.date.as_deref())
+                .and_then(DateTime::parse)
+                .is_none_or(|d| d.date() <= today)
+    }
+
     /// Compute the output path relative to the output directory.
     /// e.g., content/blog/foo.md → blog/foo.html
-    pub fn output_path(&self, content_root: &Path) -> PathBuf {
+    ///
+    /// A `permalink` wins over the source location: "/blog/2024/foo/" →
+    /// blog/2024/foo/index.html, "/blog/2024/foo.html" → blog/2024/foo.html.
+    pub fn output_path(&self, content_root: &Path, style: UrlStyle) -> PathBuf {
+        if let Some(permalink) = &self.permalink {
+            let trimmed = permalink.trim_matches('/');
+            return if permalink.ends_with(".html") {
+                PathBuf::from(trimmed)
+            } else {
+                Path::new(trimmed).join("index.html")
+            };
+        }
+
         let relative = self
             .source_path
             .strip_prefix(content_root)
             .unwrap_or(&self.source_path);
-
-        match self.kind {
-            ContentKind::Section => {
-                // _index.md → parent/index.html (listing pages stay as index.html)
-                let parent = relative.parent().unwrap_or(Path::new(""));
-                parent.join("index.html")
-            }
-            _ => {
-                // Regular content → parent/slug.html (flat structure)
-                let parent = relative.parent().unwrap_or(Path::new(""));
-                parent.join(format!("{}.html", self.slug))
-            }
+        let parent = relative.parent().unwrap_or(Path::new(""));
+
+        match (&self.kind, style) {
+            // _index.md → parent/index.html (listing pages stay as index.html)
+            (ContentKind::Section, _) => parent.join("index.html"),
+            // Regular content → parent/slug.html (flat structure)
+            (_, UrlStyle::Flat) => parent.join(format!("{}.html", self.slug)),
+            // Regular content → parent/slug/index.html (pretty URLs)
+            (_, UrlStyle::Pretty) => parent.join(&self.slug).join("index.html"),
         }
     }
+
+    /// URL path linking to this content (e.g., "/blog/post.html" or "/blog/post/").
+    pub fn url_path(&self, content_root: &Path, style: UrlStyle) -> String {
+        style.link(&format!(
+            "/{}",
+            self.output_path(content_root, style).display()
+        ))
+    }
 }
 
 fn parse_frontmatter(path: &Path, parsed: &gray_matter::ParsedEntity) -> Result<Frontmatter> {
@@ -146,137 +357,368 @@ fn parse_frontmatter(path: &Path, parsed: &gray_matter::ParsedEntity) -> Result<
         })?;
 
     let description = pod.get("description").and_then(|v| v.as_string().ok());
-    let date = pod.get("date").and_then(|v| v.as_string().ok());
+    let date = parse_date_field(path, &pod, "date")?;
+    let updated = parse_date_field(path, &pod, "updated")?;
     let weight = pod.get("weight").and_then(|v| v.as_i64().ok());
     let link_to = pod.get("link_to").and_then(|v| v.as_string().ok());
     let nav_label = pod.get("nav_label").and_then(|v| v.as_string().ok());
     let section_type = pod.get("section_type").and_then(|v| v.as_string().ok());
     let template = pod.get("template").and_then(|v| v.as_string().ok());
     let toc = pod.get("toc").and_then(|v| v.as_bool().ok());
+    let toc_level = |field: &str| {
+        pod.get(field)
+            .and_then(|v| v.as_i64().ok())
+            .map(|value| match u8::try_from(value) {
+                Ok(level @ 1..=6) => Ok(level),
+                _ => Err(Error::Frontmatter {
+                    path: path.to_path_buf(),
+                    message: format!("invalid {field} {value} (expected 1 to 6)"),
+                }),
+            })
+            .transpose()
+    };
+    let toc_min_level = toc_level("toc_min_level")?;
+    let toc_max_level = toc_level("toc_max_level")?;
+    let draft = pod
+        .get("draft")
+        .and_then(|v| v.as_bool().ok())
+        .unwrap_or(false);
+    let publish_date = parse_date_field(path, &pod, "publish_date")?;
+    let paginate_by = pod
+        .get("paginate_by")
+        .and_then(|v| v.as_i64().ok())
+        .and_then(|n| usize::try_from(n).ok());
+    let sort_by = pod
+        .get("sort_by")
+        .and_then(|v| v.as_string().ok())
+        .map(|value| {
+            SortBy::parse(&value).ok_or_else(|| Error::Frontmatter {
+                path: path.to_path_buf(),
+                message: format!(
+                    "invalid sort_by \"{value}\" (expected date, weight, title or slug)"
+                ),
+            })
+        })
+        .transpose()?;
+    let sort_order = pod
+        .get("sort_order")
+        .and_then(|v| v.as_string().ok())
+        .map(|value| {
+            SortOrder::parse(&value).ok_or_else(|| Error::Frontmatter {
+                path: path.to_path_buf(),
+                message: format!("invalid sort_order \"{value}\" (expected asc or desc)"),
+            })
+        })
+        .transpose()?;
+    let feed = pod.get("feed").and_then(|v| v.as_bool().ok());
+    let section_feed = pod
+        .get("section_feed")
+        .and_then(|v| v.as_bool().ok())
+        .unwrap_or(false);
+    let sitemap = pod
+        .get("sitemap")
+        .and_then(|v| v.as_bool().ok())
+        .unwrap_or(true);
+    let noindex = pod
+        .get("noindex")
+        .and_then(|v| v.as_bool().ok())
+        .unwrap_or(false);
+    let changefreq = pod
+        .get("changefreq")
+        .and_then(|v| v.as_string().ok())
+        .map(|value| {
+            ChangeFreq::parse(&value).ok_or_else(|| Error::Frontmatter {
+                path: path.to_path_buf(),
+                message: format!(
+                    "invalid changefreq \"{value}\" (expected always, hourly, daily, weekly, monthly, yearly or never)"
+                ),
+            })
+        })
+        .transpose()?;
+    let priority = pod
+        .get("priority")
+        .and_then(|v| {
+            v.as_f64()
+                .ok()
+                .or_else(|| v.as_i64().ok().map(|n| n as f64))
+        })
+        .map(|value| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(value)
+            } else {
+                Err(Error::Frontmatter {
+                    path: path.to_path_buf(),
+                    message: format!("invalid priority {value} (expected 0.0 to 1.0)"),
+                })
+            }
+        })
+        .transpose()?;
 
-    // Handle nested taxonomies.tags structure
-    let tags = if let Some(taxonomies) = pod.get("taxonomies") {
-        if let Ok(tax_map) = taxonomies.as_hashmap() {
-            if let Some(tags_pod) = tax_map.get("tags") {
-                if let Ok(tags_vec) = tags_pod.as_vec() {
-                    tags_vec.iter().filter_map(|v| v.as_string().ok()).collect()
-                } else {
-                    Vec::new()
-                }
+    let slug = pod
+        .get("slug")
+        .and_then(|v| v.as_string().ok())
+        .map(|value| {
+            if value.is_empty() || value == "." || value == ".." || value.contains(['/', '\\']) {
+                Err(Error::Frontmatter {
+                    path: path.to_path_buf(),
+                    message: format!("invalid slug \"{value}\" (expected a single path segment)"),
+                })
             } else {
-                Vec::new()
+                Ok(value)
             }
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
-    };
+        })
+        .transpose()?;
+    let permalink = pod
+        .get("permalink")
+        .and_then(|v| v.as_string().ok())
+        .map(|value| match check_permalink(&value) {
+            Ok(()) => Ok(value),
+            Err(message) => Err(Error::Frontmatter {
+                path: path.to_path_buf(),
+                message: format!("invalid permalink \"{value}\": {message}"),
+            }),
+        })
+        .transpose()?;
+
+    // A single string is a one-alias list
+    let aliases = pod
+        .get("aliases")
+        .map(|v| match v.as_vec() {
+            Ok(list) => list.iter().filter_map(|v| v.as_string().ok()).collect(),
+            Err(_) => v.as_string().map(|s| vec![s]).unwrap_or_default(),
+        })
+        .unwrap_or_default();
+
+    // Handle nested taxonomies.<name> lists (a single string is a one-term list)
+    let taxonomies: BTreeMap<String, Vec<String>> = pod
+        .get("taxonomies")
+        .and_then(|v| v.as_hashmap().ok())
+        .map(|tax_map| {
+            tax_map
+                .into_iter()
+                .map(|(name, terms_pod)| {
+                    let terms = match terms_pod.as_vec() {
+                        Ok(terms_vec) => terms_vec
+                            .iter()
+                            .filter_map(|v| v.as_string().ok())
+                            .collect(),
+                        Err(_) => terms_pod.as_string().map(|s| vec![s]).unwrap_or_default(),
+                    };
+                    (name, terms)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let tags = taxonomies.get("tags").cloned().unwrap_or_default();
 
     Ok(Frontmatter {
         title,
         description,
         date,
+        updated,
         tags,
+        taxonomies,
         weight,
         link_to,
         nav_label,
         section_type,
         template,
         toc,
+        toc_min_level,
+        toc_max_level,
+        draft,
+        publish_date,
+        paginate_by,
+        sort_by,
+        sort_order,
+        feed,
+        section_feed,
+        sitemap,
+        noindex,
+        changefreq,
+        priority,
+        aliases,
+        slug,
+        permalink,
     })
 }
 
-/// Discover navigation items from the content directory structure.
+/// Placeholders allowed in a section `permalink` pattern.
+const PERMALINK_PLACEHOLDERS: [&str; 5] = ["year", "month", "day", "slug", "section"];
+
+/// Check a permalink pattern: site-relative, known placeholders, no `..`.
+fn check_permalink(pattern: &str) -> std::result::Result<(), String> {
+    if !pattern.starts_with('/') {
+        return Err("expected a path starting with '/'".to_string());
+    }
+    if pattern.split('/').any(|seg| seg == "." || seg == "..") {
+        return Err("'.' and '..' segments are not allowed".to_string());
+    }
+    for placeholder in pattern.split(':').skip(1) {
+        let name: String = placeholder
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .collect();
+        if !PERMALINK_PLACEHOLDERS.contains(&name.as_str()) {
+            return Err(format!(
+                "unknown placeholder \":{name}\" (expected :year, :month, :day, :slug or :section)"
+            ));
+        }
+    }
+    if !pattern.contains(":slug") {
+        return Err("missing :slug placeholder".to_string());
+    }
+    Ok(())
+}
+
+/// Expand a permalink pattern for an item of `section`.
+///
+/// Date placeholders come from the item's `date`; a non-`.html` result
+/// always ends with `/`.
+fn expand_permalink(pattern: &str, section: &str, item: &Content) -> Result<String> {
+    let date = item.frontmatter.date.as_deref().map(date::date_part);
+    let needs_date = [":year", ":month", ":day"]
+        .iter()
+        .any(|p| pattern.contains(p));
+    let date = match date {
+        Some(date) => date,
+        None if needs_date => {
+            return Err(Error::Frontmatter {
+                path: item.source_path.clone(),
+                message: format!("missing 'date' required by the section permalink \"{pattern}\""),
+            });
+        }
+        None => "",
+    };
+
+    let mut url = pattern
+        .replace(":year", date.get(..4).unwrap_or_default())
+        .replace(":month", date.get(5..7).unwrap_or_default())
+        .replace(":day", date.get(8..10).unwrap_or_default())
+        .replace(":section", section)
+        .replace(":slug", &item.slug);
+    if !url.ends_with(".html") && !url.ends_with('/') {
+        url.push('/');
+    }
+    Ok(url)
+}
+
+/// Read an optional date field, rejecting values that are not a date or
+/// RFC 3339 datetime.
+fn parse_date_field(path: &Path, pod: &HashMap<String, Pod>, key: &str) -> Result<Option<String>> {
+    let Some(value) = pod.get(key).and_then(|v| v.as_string().ok()) else {
+        return Ok(None);
+    };
+    if DateTime::parse(&value).is_none() {
+        return Err(Error::Frontmatter {
+            path: path.to_path_buf(),
+            message: format!(
+                "invalid {key} \"{value}\" (expected YYYY-MM-DD or an RFC 3339 datetime)"
+            ),
+        });
+    }
+    Ok(Some(value))
+}
+
+/// Whether discovered content should be built.
+///
+/// Drafts and future-dated items are skipped unless `include_drafts` is set.
+fn is_included(content: &Content, include_drafts: bool) -> bool {
+    include_drafts || content.is_published(date::today())
+}
+
+/// Build navigation items from discovered content.
 ///
 /// Rules:
-/// - Top-level `.md` files (except `_index.md`) become nav items (pages)
-/// - Directories containing `_index.md` become nav items (sections)
+/// - Standalone `pages` become nav items
+/// - Top-level sections become nav items, with their items and subsections
+///   as children, recursively
 /// - Items are sorted by weight (lower first), then alphabetically by label
-pub fn discover_nav(content_dir: &Path) -> Result<Vec<NavItem>> {
-    let mut nav_items = Vec::new();
-
-    // Read top-level entries in content directory
-    let entries = fs::read_dir(content_dir).map_err(|e| Error::ReadFile {
-        path: content_dir.to_path_buf(),
-        source: e,
-    })?;
+/// - Paths are links in the given URL `style`
+///
+/// `sections` and `section_items` are in manifest order, so drafts and
+/// future-dated content are already left out.
+pub fn build_nav(
+    content_dir: &Path,
+    sections: &[Section],
+    section_items: &[Vec<Content>],
+    pages: &[Content],
+    style: UrlStyle,
+) -> Vec<NavItem> {
+    let mut nav_items: Vec<NavItem> = pages
+        .iter()
+        .map(|page| content_nav_item(content_dir, page, style))
+        .collect();
+
+    for (section, items) in sections.iter().zip(section_items) {
+        if section.parent_name().is_none() {
+            nav_items.push(section_nav_item(
+                content_dir,
+                section,
+                items,
+                sections,
+                section_items,
+                style,
+            ));
+        }
+    }
 
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
+    sort_nav(&mut nav_items);
+    nav_items
+}
 
-        if path.is_file() {
-            // Top-level .md file (except _index.md) → page nav item
-            if path.extension().is_some_and(|ext| ext == "md") {
-                let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                if file_name != "_index.md" {
-                    let content = Content::from_path(&path, ContentKind::Page)?;
-                    let slug = path.file_stem().and_then(|s| s.to_str()).unwrap_or("page");
-                    nav_items.push(NavItem {
-                        label: content
-                            .frontmatter
-                            .nav_label
-                            .unwrap_or(content.frontmatter.title),
-                        path: format!("/{}.html", slug),
-                        weight: content.frontmatter.weight.unwrap_or(DEFAULT_WEIGHT),
-                        children: Vec::new(),
-                    });
-                }
-            }
-        } else if path.is_dir() {
-            // Directory with _index.md → section nav item
-            let index_path = path.join("_index.md");
-            if index_path.exists() {
-                let content = Content::from_path(&index_path, ContentKind::Section)?;
-                let dir_name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("section");
-
-                // Collect section items as child nav items
-                let section = Section {
-                    index: content.clone(),
-                    name: dir_name.to_string(),
-                    section_type: content
-                        .frontmatter
-                        .section_type
-                        .clone()
-                        .unwrap_or_else(|| dir_name.to_string()),
-                    path: path.clone(),
-                };
+/// Build the nav item for a page or section item.
+fn content_nav_item(content_dir: &Path, content: &Content, style: UrlStyle) -> NavItem {
+    let fm = &content.frontmatter;
+    NavItem {
+        label: fm.nav_label.clone().unwrap_or_else(|| fm.title.clone()),
+        path: content.url_path(content_dir, style),
+        weight: fm.weight.unwrap_or(DEFAULT_WEIGHT),
+        children: Vec::new(),
+    }
+}
 
-                let mut children: Vec<NavItem> = section
-                    .collect_items()?
-                    .into_iter()
-                    .map(|item| NavItem {
-                        label: item.frontmatter.nav_label.unwrap_or(item.frontmatter.title),
-                        path: format!("/{}/{}.html", dir_name, item.slug),
-                        weight: item.frontmatter.weight.unwrap_or(DEFAULT_WEIGHT),
-                        children: Vec::new(),
-                    })
-                    .collect();
-
-                // Sort children by weight, then alphabetically
-                children
-                    .sort_by(|a, b| a.weight.cmp(&b.weight).then_with(|| a.label.cmp(&b.label)));
-
-                nav_items.push(NavItem {
-                    label: content
-                        .frontmatter
-                        .nav_label
-                        .unwrap_or(content.frontmatter.title),
-                    path: format!("/{}/index.html", dir_name),
-                    weight: content.frontmatter.weight.unwrap_or(DEFAULT_WEIGHT),
-                    children,
-                });
-            }
+/// Build the nav item for a section, with its items and subsections as children.
+fn section_nav_item(
+    content_dir: &Path,
+    section: &Section,
+    items: &[Content],
+    sections: &[Section],
+    section_items: &[Vec<Content>],
+    style: UrlStyle,
+) -> NavItem {
+    let mut children: Vec<NavItem> = items
+        .iter()
+        .map(|item| content_nav_item(content_dir, item, style))
+        .collect();
+
+    for (subsection, subitems) in sections.iter().zip(section_items) {
+        if subsection.parent_name() == Some(section.name.as_str()) {
+            children.push(section_nav_item(
+                content_dir,
+                subsection,
+                subitems,
+                sections,
+                section_items,
+                style,
+            ));
         }
     }
 
-    // Sort by weight, then alphabetically by label
-    nav_items.sort_by(|a, b| a.weight.cmp(&b.weight).then_with(|| a.label.cmp(&b.label)));
+    sort_nav(&mut children);
+
+    let fm = &section.index.frontmatter;
+    NavItem {
+        label: fm.nav_label.clone().unwrap_or_else(|| fm.title.clone()),
+        path: style.link(&format!("/{}/index.html", section.name)),
+        weight: fm.weight.unwrap_or(DEFAULT_WEIGHT),
+        children,
+    }
+}
 
-    Ok(nav_items)
+/// Sort nav items by weight, then alphabetically by label.
+fn sort_nav(items: &mut [NavItem]) {
+    items.sort_by(|a, b| a.weight.cmp(&b.weight).then_with(|| a.label.cmp(&b.label)));
 }
 
 /// A discovered section from the content directory.
@@ -284,16 +726,20 @@ pub fn discover_nav(content_dir: &Path) -> Result<Vec<NavItem>> {
 pub struct Section {
     /// The section's index content (_index.md)
     pub index: Content,
-    /// Directory name (e.g., "blog", "projects")
+    /// Directory path relative to the content root (e.g., "blog", "docs/guides")
     pub name: String,
-    /// Section type for template dispatch (from frontmatter or directory name)
+    /// Section type for template dispatch (from frontmatter, the parent
+    /// section, or the directory name)
     pub section_type: String,
     /// Path to section directory
     pub path: PathBuf,
+    /// Whether drafts and future-dated items are collected
+    pub include_drafts: bool,
 }
 
 impl Section {
     /// Collect all content items in this section (excluding _index.md).
+    /// Drafts and future-dated items are skipped unless `include_drafts` is set.
     pub fn collect_items(&self) -> Result<Vec<Content>> {
         let mut items = Vec::new();
 
@@ -309,26 +755,146 @@ impl Section {
                 && path.extension().is_some_and(|ext| ext == "md")
                 && path.file_name().is_some_and(|n| n != "_index.md")
             {
-                // Determine content kind based on section type
-                let kind = match self.section_type.as_str() {
-                    "blog" => ContentKind::Post,
-                    "projects" => ContentKind::Project,
-                    _ => ContentKind::Page,
+                // Determine content kind based on feed membership and section type
+                let kind = if self.has_feed() {
+                    ContentKind::Post
+                } else if self.section_type == "projects" {
+                    ContentKind::Project
+                } else {
+                    ContentKind::Page
                 };
-                items.push(Content::from_path(&path, kind)?);
+                let mut item = Content::from_path(&path, kind)?;
+                if !is_included(&item, self.include_drafts) {
+                    continue;
+                }
+                if let Some(pattern) = &self.index.frontmatter.permalink {
+                    item.permalink = Some(expand_permalink(pattern, &self.name, &item)?);
+                }
+                items.push(item);
             }
         }
 
         Ok(items)
     }
+
+    /// Whether this section's items are included in feeds.
+    ///
+    /// Set with `feed` in `_index.md`; defaults to true for "blog" sections.
+    pub fn has_feed(&self) -> bool {
+        self.index
+            .frontmatter
+            .feed
+            .unwrap_or(self.section_type == "blog")
+    }
+
+    /// Sort items by the `sort_by` and `sort_order` of `_index.md`.
+    ///
+    /// Without `sort_by`, feed sections sort by date and all others by weight
+    /// ("projects" sections put unweighted items last). Ties sort by title.
+    pub fn sort_items(&self, items: &mut [Content]) {
+        let fm = &self.index.frontmatter;
+        let sort_by = fm.sort_by.unwrap_or(if self.has_feed() {
+            SortBy::Date
+        } else {
+            SortBy::Weight
+        });
+        let sort_order = fm.sort_order.unwrap_or(sort_by.default_order());
+        let default_weight = if self.section_type == "projects" {
+            DEFAULT_WEIGHT_HIGH
+        } else {
+            DEFAULT_WEIGHT
+        };
+
+        items.sort_by(|a, b| {
+            let (fa, fb) = (&a.frontmatter, &b.frontmatter);
+            let ordering = match sort_by {
+                SortBy::Date => fa.date_time().cmp(&fb.date_time()),
+                SortBy::Weight => fa
+                    .weight
+                    .unwrap_or(default_weight)
+                    .cmp(&fb.weight.unwrap_or(default_weight)),
+                SortBy::Title => fa.title.cmp(&fb.title),
+                SortBy::Slug => a.slug.cmp(&b.slug),
+            };
+            let ordering = match sort_order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            };
+            ordering.then_with(|| fa.title.cmp(&fb.title))
+        });
+    }
+
+    /// Name of the enclosing section, if this is a subsection.
+    pub fn parent_name(&self) -> Option<&str> {
+        self.name.rsplit_once('/').map(|(parent, _)| parent)
+    }
+
+    /// URL path of the section's own feed in `format` (e.g., "/blog/feed.xml").
+    pub fn feed_path(&self, format: FeedFormat) -> String {
+        format!("/{}/{}", self.name, format.file_name())
+    }
+
+    /// Pages of the section listing, split by the index's `paginate_by`.
+    pub fn pages(&self, total_items: usize) -> Vec<Pager> {
+        let dir = format!("/{}", self.name);
+        pagination::paginate(&dir, total_items, self.index.frontmatter.paginate_by)
+    }
 }
 
 /// Discover all sections (directories with _index.md) in the content directory.
-pub fn discover_sections(content_dir: &Path) -> Result<Vec<Section>> {
+///
+/// Subsections are discovered recursively; a directory without `_index.md`
+/// ends the descent. Sections are listed depth-first, each followed by its
+/// subsections, with siblings sorted by weight.
+pub fn discover_sections(content_dir: &Path, include_drafts: bool) -> Result<Vec<Section>> {
     let mut sections = Vec::new();
+    collect_sections(
+        content_dir,
+        content_dir,
+        None,
+        include_drafts,
+        &mut sections,
+    )?;
+    Ok(sections)
+}
 
-    let entries = fs::read_dir(content_dir).map_err(|e| Error::ReadFile {
-        path: content_dir.to_path_buf(),
+/// Append the sections under `dir`, and recursively their subsections.
+fn collect_sections(
+    content_dir: &Path,
+    dir: &Path,
+    parent_type: Option<&str>,
+    include_drafts: bool,
+    sections: &mut Vec<Section>,
+) -> Result<()> {
+    for section in child_sections(content_dir, dir, parent_type, include_drafts)? {
+        let path = section.path.clone();
+        let section_type = section.section_type.clone();
+        sections.push(section);
+        collect_sections(
+            content_dir,
+            &path,
+            Some(&section_type),
+            include_drafts,
+            sections,
+        )?;
+    }
+    Ok(())
+}
+
+/// Sections directly under `dir` (not recursive), sorted by weight.
+///
+/// Subsections without a `section_type` inherit `parent_type`. Draft and
+/// future-dated sections are skipped unless `include_drafts` is set.
+fn child_sections(
+    content_dir: &Path,
+    dir: &Path,
+    parent_type: Option<&str>,
+    include_drafts: bool,
+) -> Result<Vec<Section>> {
+    let mut sections = Vec::new();
+
+    let entries = fs::read_dir(dir).map_err(|e| Error::ReadFile {
+        path: dir.to_path_buf(),
         source: e,
     })?;
 
@@ -339,24 +905,36 @@ pub fn discover_sections(content_dir: &Path) -> Result<Vec<Section>> {
             let index_path = path.join("_index.md");
             if index_path.exists() {
                 let index = Content::from_path(&index_path, ContentKind::Section)?;
-                let name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("section")
-                    .to_string();
-
-                // Section type from frontmatter, or fall back to directory name
+                // A draft or future section hides its whole subtree
+                if !is_included(&index, include_drafts) {
+                    continue;
+                }
+                let relative = path.strip_prefix(content_dir).unwrap_or(&path);
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                // Section type from frontmatter, the parent section, or the directory name
                 let section_type = index
                     .frontmatter
                     .section_type
                     .clone()
-                    .unwrap_or_else(|| name.clone());
+                    .or_else(|| parent_type.map(str::to_string))
+                    .unwrap_or_else(|| {
+                        path.file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("section")
+                            .to_string()
+                    });
 
                 sections.push(Section {
                     index,
                     name,
                     section_type,
                     path,
+                    include_drafts,
                 });
             }
         }
@@ -373,7 +951,7 @@ pub fn discover_sections(content_dir: &Path) -> Result<Vec<Section>> {
 }
 
 /// Discover standalone pages (top-level .md files except _index.md).
-pub fn discover_pages(content_dir: &Path) -> Result<Vec<Content>> {
+pub fn discover_pages(content_dir: &Path, include_drafts: bool) -> Result<Vec<Content>> {
     let mut pages = Vec::new();
 
     let entries = fs::read_dir(content_dir).map_err(|e| Error::ReadFile {
@@ -383,17 +961,37 @@ pub fn discover_pages(content_dir: &Path) -> Result<Vec<Content>> {
 
     for entry in entries.filter_map(|e| e.ok()) {
         let path = entry.path();
-        if path.is_file()
-            && path.extension().is_some_and(|ext| ext == "md")
-            && path.file_name().is_some_and(|n| n != "_index.md")
-        {
-            pages.push(Content::from_path(&path, ContentKind::Page)?);
+        if is_standalone_page(&path) {
+            let page = Content::from_path(&path, ContentKind::Page)?;
+            if is_included(&page, include_drafts) {
+                pages.push(page);
+            }
         }
     }
 
     Ok(pages)
 }
 
+/// Whether a top-level path is a standalone page: a .md file other than the
+/// homepage (`_index.md`) and the 404 page (`404.md`).
+fn is_standalone_page(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|ext| ext == "md")
+        && path
+            .file_name()
+            .is_some_and(|n| n != "_index.md" && n != NOT_FOUND_FILE)
+}
+
+/// Load the custom 404 page (`content/404.md`), if present and included.
+fn discover_not_found(content_dir: &Path, include_drafts: bool) -> Result<Option<Content>> {
+    let path = content_dir.join(NOT_FOUND_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let page = Content::from_path(&path, ContentKind::Page)?;
+    Ok(is_included(&page, include_drafts).then_some(page))
+}
+
 /// Complete site content manifest from a single discovery pass.
 ///
 /// Aggregates all content types for use by rendering, feed, and sitemap generation.
@@ -401,55 +999,109 @@ pub fn discover_pages(content_dir: &Path) -> Result<Vec<Content>> {
 pub struct SiteManifest {
     /// Homepage content (content/_index.md)
     pub homepage: Content,
-    /// All sections (directories with _index.md)
+    /// All sections (directories with _index.md), each followed by its subsections
     pub sections: Vec<Section>,
+    /// Sorted items of each section, in the same order as `sections`
+    pub section_items: Vec<Vec<Content>>,
     /// Standalone pages (top-level .md files)
     pub pages: Vec<Content>,
-    /// Blog posts for feed generation (items from "blog" sections)
+    /// Custom 404 page (content/404.md), kept out of nav, feeds and sitemap
+    pub not_found: Option<Content>,
+    /// Posts for feed generation (items from feed sections), newest first
     pub posts: Vec<Content>,
     /// Navigation menu items
     pub nav: Vec<NavItem>,
+    /// Declared taxonomies with at least one term
+    pub taxonomies: Vec<Taxonomy>,
 }
 
 impl SiteManifest {
     /// Discover all site content in a single pass.
-    pub fn discover(content_dir: impl AsRef<Path>) -> Result<Self> {
-        Self::discover_inner(content_dir.as_ref())
+    ///
+    /// Drafts and future-dated items are excluded unless `include_drafts` is set.
+    /// Items are grouped into each of the given `taxonomies`, and nav paths
+    /// are links in the given URL `style`.
+    pub fn discover(
+        content_dir: impl AsRef<Path>,
+        include_drafts: bool,
+        taxonomies: &[TaxonomyConfig],
+        style: UrlStyle,
+    ) -> Result<Self> {
+        Self::discover_inner(content_dir.as_ref(), include_drafts, taxonomies, style)
     }
 
-    fn discover_inner(content_dir: &Path) -> Result<Self> {
+    fn discover_inner(
+        content_dir: &Path,
+        include_drafts: bool,
+        taxonomies: &[TaxonomyConfig],
+        style: UrlStyle,
+    ) -> Result<Self> {
         // Load homepage
         let homepage_path = content_dir.join("_index.md");
         let homepage = Content::from_path(&homepage_path, ContentKind::Section)?;
 
-        // Discover navigation
-        let nav = discover_nav(content_dir)?;
-
         // Discover sections
-        let sections = discover_sections(content_dir)?;
+        let sections = discover_sections(content_dir, include_drafts)?;
 
-        // Collect section items and identify blog posts
+        // Collect and sort section items, and identify feed posts
         let mut posts = Vec::new();
+        let mut section_items = Vec::new();
         for section in &sections {
-            if section.section_type == "blog" {
-                let mut items = section.collect_items()?;
-                // Sort blog posts by date, newest first
-                items.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
-                posts.extend(items);
+            let mut items = section.collect_items()?;
+            section.sort_items(&mut items);
+            if section.has_feed() {
+                posts.extend(items.iter().cloned());
             }
+            section_items.push(items);
         }
+        // Sort feed posts by date, newest first
+        posts.sort_by_key(|c| std::cmp::Reverse(c.frontmatter.date_time()));
+
+        // Discover standalone pages and the 404 page
+        let pages = discover_pages(content_dir, include_drafts)?;
+        let not_found = discover_not_found(content_dir, include_drafts)?;
+
+        // Navigation from the sections, items and pages found above
+        let nav = build_nav(content_dir, &sections, &section_items, &pages, style);
 
-        // Discover standalone pages
-        let pages = discover_pages(content_dir)?;
+        // Group all section items and pages by each declared taxonomy
+        let taxonomies = taxonomies
+            .iter()
+            .map(|config| Taxonomy::build(config, section_items.iter().flatten().chain(&pages)))
+            .filter(|taxonomy| !taxonomy.terms.is_empty())
+            .collect();
 
         Ok(SiteManifest {
             homepage,
             sections,
+            section_items,
             pages,
+            not_found,
             posts,
             nav,
+            taxonomies,
         })
     }
+
+    /// Every rendered content file: the homepage, each section index
+    /// followed by its items, then standalone pages.
+    pub fn content(&self) -> impl Iterator<Item = &Content> {
+        let sections = self
+            .sections
+            .iter()
+            .zip(&self.section_items)
+            .flat_map(|(section, items)| std::iter::once(&section.index).chain(items));
+        std::iter::once(&self.homepage)
+            .chain(sections)
+            .chain(&self.pages)
+    }
+
+    /// Direct subsections of `section`, in weight order.
+    pub fn subsections<'a>(&'a self, section: &'a Section) -> impl Iterator<Item = &'a Section> {
+        self.sections
+            .iter()
+            .filter(move |s| s.parent_name() == Some(section.name.as_str()))
+    }
 }
 
 #[cfg(test)]
@@ -461,6 +1113,27 @@ mod tests {
         tempfile::tempdir().expect("failed to create temp dir")
     }
 
+    /// Discover sections, items and pages, and build the nav from them.
+    fn discover_nav(
+        content_dir: &Path,
+        include_drafts: bool,
+        style: UrlStyle,
+    ) -> Result<Vec<NavItem>> {
+        let sections = discover_sections(content_dir, include_drafts)?;
+        let section_items = sections
+            .iter()
+            .map(Section::collect_items)
+            .collect::<Result<Vec<_>>>()?;
+        let pages = discover_pages(content_dir, include_drafts)?;
+        Ok(build_nav(
+            content_dir,
+            &sections,
+            &section_items,
+            &pages,
+            style,
+        ))
+    }
+
     fn write_frontmatter(path: &Path, title: &str, weight: Option<i64>, nav_label: Option<&str>) {
         let mut content = format!("---\ntitle: \"{}\"\n", title);
         if let Some(w) = weight {
@@ -481,7 +1154,7 @@ mod tests {
         // Create top-level page
         write_frontmatter(&content_dir.join("about.md"), "About Me", None, None);
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 1);
         assert_eq!(nav[0].label, "About Me");
         assert_eq!(nav[0].path, "/about.html");
@@ -497,7 +1170,7 @@ mod tests {
         fs::create_dir(&blog_dir).expect("failed to create blog dir");
         write_frontmatter(&blog_dir.join("_index.md"), "Blog", None, None);
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 1);
         assert_eq!(nav[0].label, "Blog");
         assert_eq!(nav[0].path, "/blog/index.html");
@@ -512,7 +1185,7 @@ mod tests {
         write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
         write_frontmatter(&content_dir.join("about.md"), "About", None, None);
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 1);
         assert_eq!(nav[0].label, "About");
     }
@@ -526,7 +1199,7 @@ mod tests {
         write_frontmatter(&content_dir.join("contact.md"), "Contact", Some(10), None);
         write_frontmatter(&content_dir.join("blog.md"), "Blog", Some(20), None);
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 3);
         assert_eq!(nav[0].label, "Contact"); // weight 10
         assert_eq!(nav[1].label, "Blog"); // weight 20
@@ -545,7 +1218,7 @@ mod tests {
             Some("About"),
         );
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 1);
         assert_eq!(nav[0].label, "About"); // Uses nav_label, not title
     }
@@ -572,7 +1245,7 @@ mod tests {
             None,
         );
 
-        let nav = discover_nav(content_dir).expect("discover_nav failed");
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
         assert_eq!(nav.len(), 1);
         assert_eq!(nav[0].label, "Features");
         assert_eq!(nav[0].children.len(), 2);
@@ -621,7 +1294,7 @@ mod tests {
             None,
         );
 
-        let sections = discover_sections(content_dir).expect("discover_sections failed");
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
         assert_eq!(sections.len(), 2);
 
         let names: Vec<_> = sections.iter().map(|s| s.name.as_str()).collect();
@@ -642,7 +1315,7 @@ mod tests {
             None,
         );
 
-        let sections = discover_sections(content_dir).expect("discover_sections failed");
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
         assert_eq!(sections.len(), 1);
         assert_eq!(sections[0].name, "writings");
         assert_eq!(sections[0].section_type, "blog"); // From frontmatter, not dir name
@@ -661,7 +1334,7 @@ mod tests {
             None,
         );
 
-        let sections = discover_sections(content_dir).expect("discover_sections failed");
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
         assert_eq!(sections.len(), 1);
         assert_eq!(sections[0].section_type, "gallery"); // Falls back to dir name
     }
@@ -682,12 +1355,148 @@ mod tests {
             Some(10),
         );
 
-        let sections = discover_sections(content_dir).expect("discover_sections failed");
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
         assert_eq!(sections.len(), 2);
         assert_eq!(sections[0].name, "projects"); // weight 10
         assert_eq!(sections[1].name, "blog"); // weight 20
     }
 
+    #[test]
+    fn test_discover_sections_recurses() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        fs::create_dir_all(content_dir.join("docs/guides/advanced")).unwrap();
+        fs::create_dir(content_dir.join("docs/reference")).unwrap();
+        fs::create_dir(content_dir.join("docs/assets")).unwrap(); // No _index.md
+        write_section_index(
+            &content_dir.join("docs/_index.md"),
+            "Docs",
+            Some("docs"),
+            None,
+        );
+        write_section_index(
+            &content_dir.join("docs/guides/_index.md"),
+            "Guides",
+            None,
+            Some(1),
+        );
+        write_section_index(
+            &content_dir.join("docs/guides/advanced/_index.md"),
+            "Advanced",
+            None,
+            None,
+        );
+        write_section_index(
+            &content_dir.join("docs/reference/_index.md"),
+            "Reference",
+            Some("api"),
+            Some(2),
+        );
+        write_frontmatter(
+            &content_dir.join("docs/guides/advanced/tuning.md"),
+            "Tuning",
+            None,
+            None,
+        );
+
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
+        let names: Vec<_> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "docs",
+                "docs/guides",
+                "docs/guides/advanced",
+                "docs/reference"
+            ]
+        );
+
+        // Subsections inherit the parent's type unless they set their own
+        assert_eq!(sections[2].section_type, "docs");
+        assert_eq!(sections[3].section_type, "api");
+        assert_eq!(sections[2].parent_name(), Some("docs/guides"));
+        assert_eq!(sections[0].parent_name(), None);
+
+        // Items keep the directory tree in their output paths
+        let items = sections[2].collect_items().unwrap();
+        assert_eq!(
+            items[0].output_path(content_dir, UrlStyle::Flat),
+            PathBuf::from("docs/guides/advanced/tuning.html")
+        );
+    }
+
+    #[test]
+    fn test_nested_sections_in_nav_and_manifest() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir_all(content_dir.join("docs/guides")).unwrap();
+        write_section_index(&content_dir.join("docs/_index.md"), "Docs", None, None);
+        write_frontmatter(&content_dir.join("docs/intro.md"), "Intro", Some(1), None);
+        write_section_index(
+            &content_dir.join("docs/guides/_index.md"),
+            "Guides",
+            None,
+            Some(2),
+        );
+        write_frontmatter(
+            &content_dir.join("docs/guides/setup.md"),
+            "Setup",
+            None,
+            None,
+        );
+
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
+        assert_eq!(nav.len(), 1);
+        let docs = &nav[0];
+        assert_eq!(docs.children.len(), 2);
+        assert_eq!(docs.children[0].path, "/docs/intro.html");
+        let guides = &docs.children[1];
+        assert_eq!(guides.path, "/docs/guides/index.html");
+        assert_eq!(guides.children[0].path, "/docs/guides/setup.html");
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        let subsections: Vec<_> = manifest
+            .subsections(&manifest.sections[0])
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(subsections, vec!["docs/guides"]);
+        assert_eq!(
+            manifest.sections[1].pages(0)[0].path,
+            "/docs/guides/index.html"
+        );
+    }
+
+    #[test]
+    fn test_pretty_url_style() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("about.md"), "About", None, None);
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        write_section_index(&content_dir.join("blog/_index.md"), "Blog", None, None);
+        write_frontmatter(&content_dir.join("blog/post.md"), "Post", None, None);
+
+        let nav = discover_nav(content_dir, false, UrlStyle::Pretty).expect("discover_nav failed");
+        assert_eq!(nav[0].path, "/about/");
+        assert_eq!(nav[1].path, "/blog/");
+        assert_eq!(nav[1].children[0].path, "/blog/post/");
+
+        let post = Content::from_path(content_dir.join("blog/post.md"), ContentKind::Post).unwrap();
+        assert_eq!(
+            post.output_path(content_dir, UrlStyle::Pretty),
+            PathBuf::from("blog/post/index.html")
+        );
+        assert_eq!(post.url_path(content_dir, UrlStyle::Pretty), "/blog/post/");
+        assert_eq!(
+            post.url_path(content_dir, UrlStyle::Flat),
+            "/blog/post.html"
+        );
+    }
+
     #[test]
     fn test_section_collect_items() {
         let dir = create_test_dir();
@@ -703,7 +1512,7 @@ mod tests {
         write_frontmatter(&content_dir.join("blog/post1.md"), "Post 1", None, None);
         write_frontmatter(&content_dir.join("blog/post2.md"), "Post 2", None, None);
 
-        let sections = discover_sections(content_dir).expect("discover_sections failed");
+        let sections = discover_sections(content_dir, false).expect("discover_sections failed");
         assert_eq!(sections.len(), 1);
 
         let items = sections[0].collect_items().expect("collect_items failed");
@@ -725,7 +1534,8 @@ mod tests {
 
         write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
 
-        let manifest = SiteManifest::discover(content_dir).expect("discover failed");
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
         assert_eq!(manifest.homepage.frontmatter.title, "Home");
     }
 
@@ -743,7 +1553,8 @@ mod tests {
             None,
         );
 
-        let manifest = SiteManifest::discover(content_dir).expect("discover failed");
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
         assert_eq!(manifest.sections.len(), 1);
         assert_eq!(manifest.sections[0].name, "blog");
     }
@@ -757,7 +1568,8 @@ mod tests {
         write_frontmatter(&content_dir.join("about.md"), "About", None, None);
         write_frontmatter(&content_dir.join("contact.md"), "Contact", None, None);
 
-        let manifest = SiteManifest::discover(content_dir).expect("discover failed");
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
         assert_eq!(manifest.pages.len(), 2);
 
         let titles: Vec<_> = manifest
@@ -769,6 +1581,26 @@ mod tests {
         assert!(titles.contains(&"Contact"));
     }
 
+    #[test]
+    fn test_manifest_separates_not_found_page() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        write_frontmatter(&content_dir.join("about.md"), "About", None, None);
+        write_frontmatter(&content_dir.join("404.md"), "Not Found", None, None);
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        assert_eq!(manifest.pages.len(), 1);
+        assert_eq!(
+            manifest.not_found.as_ref().unwrap().frontmatter.title,
+            "Not Found"
+        );
+        assert!(manifest.nav.iter().all(|n| n.label != "Not Found"));
+        assert!(manifest.content().all(|c| c.slug != "404"));
+    }
+
     #[test]
     fn test_manifest_collects_blog_posts() {
         let dir = create_test_dir();
@@ -789,7 +1621,8 @@ mod tests {
         fs::write(content_dir.join("blog/post1.md"), &post1).unwrap();
         fs::write(content_dir.join("blog/post2.md"), &post2).unwrap();
 
-        let manifest = SiteManifest::discover(content_dir).expect("discover failed");
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
         assert_eq!(manifest.posts.len(), 2);
 
         // Should be sorted by date, newest first
@@ -807,11 +1640,510 @@ mod tests {
         fs::create_dir(content_dir.join("blog")).unwrap();
         write_section_index(&content_dir.join("blog/_index.md"), "Blog", None, Some(20));
 
-        let manifest = SiteManifest::discover(content_dir).expect("discover failed");
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
         assert_eq!(manifest.nav.len(), 2);
 
         // Nav should be sorted by weight
         assert_eq!(manifest.nav[0].label, "About"); // weight 10
         assert_eq!(manifest.nav[1].label, "Blog"); // weight 20
     }
+
+    // =========================================================================
+    // Draft and scheduled content tests
+    // =========================================================================
+
+    #[test]
+    fn test_manifest_excludes_drafts_and_future_posts() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        write_section_index(
+            &content_dir.join("blog/_index.md"),
+            "Blog",
+            Some("blog"),
+            None,
+        );
+
+        let published = "---\ntitle: \"Published\"\ndate: \"2026-01-15\"\n---\nContent.";
+        let draft = "---\ntitle: \"Draft\"\ndate: \"2026-01-20\"\ndraft: true\n---\nContent.";
+        let future = "---\ntitle: \"Future\"\ndate: \"9999-01-01\"\n---\nContent.";
+        let scheduled =
+            "---\ntitle: \"Scheduled\"\ndate: \"2026-01-10\"\npublish_date: \"9999-01-01\"\n---\nContent.";
+        fs::write(content_dir.join("blog/published.md"), published).unwrap();
+        fs::write(content_dir.join("blog/draft.md"), draft).unwrap();
+        fs::write(content_dir.join("blog/future.md"), future).unwrap();
+        fs::write(content_dir.join("blog/scheduled.md"), scheduled).unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        assert_eq!(manifest.posts.len(), 1);
+        assert_eq!(manifest.posts[0].frontmatter.title, "Published");
+        assert_eq!(manifest.nav[0].children.len(), 1);
+
+        let manifest = SiteManifest::discover(content_dir, true, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        assert_eq!(manifest.posts.len(), 4);
+        assert_eq!(manifest.nav[0].children.len(), 4);
+    }
+
+    #[test]
+    fn test_publish_date_is_validated_and_compared_as_a_date() {
+        let dir = create_test_dir();
+        let path = dir.path().join("post.md");
+
+        fs::write(&path, "---\ntitle: Post\npublish_date: \"1/2/2027\"\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Post).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid publish_date \"1/2/2027\""));
+        assert!(err.to_string().contains("post.md"));
+
+        fs::write(
+            &path,
+            "---\ntitle: Post\npublish_date: 2024-01-15T23:30:00-05:00\n---\n",
+        )
+        .unwrap();
+        let content = Content::from_path(&path, ContentKind::Post).unwrap();
+        let day = |d: &str| DateTime::parse(d).unwrap();
+        assert!(!content.is_published(day("2024-01-14")));
+        assert!(content.is_published(day("2024-01-15")));
+    }
+
+    #[test]
+    fn test_discover_nav_excludes_draft_pages() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("about.md"), "About", None, None);
+        fs::write(
+            content_dir.join("wip.md"),
+            "---\ntitle: \"WIP\"\ndraft: true\n---\nBody.",
+        )
+        .unwrap();
+
+        let nav = discover_nav(content_dir, false, UrlStyle::Flat).expect("discover_nav failed");
+        assert_eq!(nav.len(), 1);
+        assert_eq!(nav[0].label, "About");
+
+        let pages = discover_pages(content_dir, false).expect("discover_pages failed");
+        assert_eq!(pages.len(), 1);
+
+        let nav = discover_nav(content_dir, true, UrlStyle::Flat).expect("discover_nav failed");
+        assert_eq!(nav.len(), 2);
+    }
+
+    #[test]
+    fn test_draft_sections_hide_their_subtree() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir_all(content_dir.join("wip/deeper")).unwrap();
+        fs::write(
+            content_dir.join("wip/_index.md"),
+            "---\ntitle: \"WIP\"\ndraft: true\n---\n",
+        )
+        .unwrap();
+        write_frontmatter(&content_dir.join("wip/item.md"), "Item", None, None);
+        write_section_index(
+            &content_dir.join("wip/deeper/_index.md"),
+            "Deeper",
+            None,
+            None,
+        );
+        fs::create_dir(content_dir.join("later")).unwrap();
+        fs::write(
+            content_dir.join("later/_index.md"),
+            "---\ntitle: \"Later\"\npublish_date: \"9999-01-01\"\n---\n",
+        )
+        .unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat).unwrap();
+        assert!(manifest.sections.is_empty());
+        assert!(manifest.nav.is_empty());
+        assert_eq!(manifest.content().count(), 1);
+
+        let manifest = SiteManifest::discover(content_dir, true, &[], UrlStyle::Flat).unwrap();
+        let mut names: Vec<_> = manifest.sections.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["later", "wip", "wip/deeper"]);
+    }
+
+    #[test]
+    fn test_manifest_collects_declared_taxonomies() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir(content_dir.join("notes")).unwrap();
+        write_section_index(&content_dir.join("notes/_index.md"), "Notes", None, None);
+
+        let tagged = "---\ntitle: \"Tagged\"\ntaxonomies:\n  tags: [\"rust\", \"nix\"]\n  authors: \"Ada\"\n---\nBody.";
+        fs::write(content_dir.join("notes/tagged.md"), tagged).unwrap();
+        let page = "---\ntitle: \"About\"\ntaxonomies:\n  tags: [\"rust\"]\n  series: [\"intro\"]\n---\nBody.";
+        fs::write(content_dir.join("about.md"), page).unwrap();
+
+        let declared = [TaxonomyConfig::new("tags"), TaxonomyConfig::new("authors")];
+        let manifest = SiteManifest::discover(content_dir, false, &declared, UrlStyle::Flat)
+            .expect("discover failed");
+
+        // Undeclared "series" is parsed but gets no taxonomy
+        let names: Vec<_> = manifest
+            .taxonomies
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["tags", "authors"]);
+
+        let tags = &manifest.taxonomies[0];
+        assert_eq!(tags.terms.len(), 2);
+        assert_eq!(tags.terms[1].slug, "rust");
+        assert_eq!(tags.terms[1].items.len(), 2);
+
+        let authors = &manifest.taxonomies[1];
+        assert_eq!(authors.terms.len(), 1);
+        assert_eq!(authors.terms[0].name, "Ada");
+    }
+
+    #[test]
+    fn test_parse_taxonomies_map() {
+        let dir = create_test_dir();
+        let path = dir.path().join("post.md");
+        fs::write(
+            &path,
+            "---\ntitle: \"Post\"\ntaxonomies:\n  tags: [\"a\", \"b\"]\n  series: \"intro\"\n---\nBody.",
+        )
+        .unwrap();
+
+        let content = Content::from_path(&path, ContentKind::Post).unwrap();
+        let fm = &content.frontmatter;
+        assert_eq!(fm.tags, vec!["a", "b"]);
+        assert_eq!(fm.terms("tags"), ["a", "b"]);
+        assert_eq!(fm.terms("series"), ["intro"]);
+        assert!(fm.terms("missing").is_empty());
+    }
+
+    #[test]
+    fn test_section_pages_use_paginate_by() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        fs::write(
+            content_dir.join("blog/_index.md"),
+            "---\ntitle: \"Blog\"\npaginate_by: 2\n---\n",
+        )
+        .unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        let blog = &manifest.sections[0];
+        assert_eq!(blog.index.frontmatter.paginate_by, Some(2));
+
+        let pages = blog.pages(5);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].path, "/blog/index.html");
+        assert_eq!(pages[1].path, "/blog/page/2/index.html");
+    }
+
+    fn write_item(path: &Path, title: &str, date: &str, weight: Option<i64>) {
+        let mut content = format!("---\ntitle: \"{}\"\ndate: {}\n", title, date);
+        if let Some(w) = weight {
+            content.push_str(&format!("weight: {}\n", w));
+        }
+        content.push_str("---\nBody.");
+        fs::write(path, content).expect("failed to write test item");
+    }
+
+    fn sorted_titles(content_dir: &Path, index: &str) -> Vec<String> {
+        fs::write(content_dir.join("notes/_index.md"), index).unwrap();
+        let sections = discover_sections(content_dir, false).unwrap();
+        let mut items = sections[0].collect_items().unwrap();
+        sections[0].sort_items(&mut items);
+        items.into_iter().map(|c| c.frontmatter.title).collect()
+    }
+
+    #[test]
+    fn test_section_sort_by_and_order() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+        fs::create_dir(content_dir.join("notes")).unwrap();
+        write_item(
+            &content_dir.join("notes/b.md"),
+            "Beta",
+            "2024-01-02",
+            Some(1),
+        );
+        write_item(
+            &content_dir.join("notes/a.md"),
+            "Alpha",
+            "2024-01-03",
+            Some(3),
+        );
+        write_item(
+            &content_dir.join("notes/c.md"),
+            "Gamma",
+            "2024-01-01",
+            Some(2),
+        );
+
+        // Default: weight ascending
+        let titles = sorted_titles(content_dir, "---\ntitle: Notes\n---\n");
+        assert_eq!(titles, vec!["Beta", "Gamma", "Alpha"]);
+
+        // Date defaults to newest first
+        let titles = sorted_titles(content_dir, "---\ntitle: Notes\nsort_by: date\n---\n");
+        assert_eq!(titles, vec!["Alpha", "Beta", "Gamma"]);
+
+        let titles = sorted_titles(
+            content_dir,
+            "---\ntitle: Notes\nsort_by: date\nsort_order: asc\n---\n",
+        );
+        assert_eq!(titles, vec!["Gamma", "Beta", "Alpha"]);
+
+        let titles = sorted_titles(
+            content_dir,
+            "---\ntitle: Notes\nsort_by: slug\nsort_order: desc\n---\n",
+        );
+        assert_eq!(titles, vec!["Gamma", "Beta", "Alpha"]);
+
+        let titles = sorted_titles(content_dir, "---\ntitle: Notes\nsort_by: title\n---\n");
+        assert_eq!(titles, vec!["Alpha", "Beta", "Gamma"]);
+    }
+
+    #[test]
+    fn test_section_sort_rejects_unknown_values() {
+        let dir = create_test_dir();
+        let path = dir.path().join("_index.md");
+
+        fs::write(&path, "---\ntitle: Notes\nsort_by: color\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Section).unwrap_err();
+        assert!(err.to_string().contains("invalid sort_by"));
+
+        fs::write(&path, "---\ntitle: Notes\nsort_order: up\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Section).unwrap_err();
+        assert!(err.to_string().contains("invalid sort_order"));
+    }
+
+    #[test]
+    fn test_feed_flag_collects_posts_from_any_section() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir(content_dir.join("journal")).unwrap();
+        fs::write(
+            content_dir.join("journal/_index.md"),
+            "---\ntitle: Journal\nfeed: true\n---\n",
+        )
+        .unwrap();
+        write_item(
+            &content_dir.join("journal/day1.md"),
+            "Day 1",
+            "2024-01-01",
+            None,
+        );
+        write_item(
+            &content_dir.join("journal/day2.md"),
+            "Day 2",
+            "2024-01-02",
+            None,
+        );
+
+        // A "blog" directory can opt out
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        fs::write(
+            content_dir.join("blog/_index.md"),
+            "---\ntitle: Blog\nfeed: false\n---\n",
+        )
+        .unwrap();
+        write_item(&content_dir.join("blog/old.md"), "Old", "2023-01-01", None);
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat)
+            .expect("discover failed");
+        let titles: Vec<_> = manifest
+            .posts
+            .iter()
+            .map(|c| c.frontmatter.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Day 2", "Day 1"]);
+        assert_eq!(manifest.posts[0].kind, ContentKind::Post);
+    }
+
+    #[test]
+    fn test_section_feed_flag() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        write_frontmatter(&content_dir.join("_index.md"), "Home", None, None);
+        fs::create_dir(content_dir.join("changelog")).unwrap();
+        fs::write(
+            content_dir.join("changelog/_index.md"),
+            "---\ntitle: Changelog\nsection_feed: true\n---\n",
+        )
+        .unwrap();
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        write_section_index(&content_dir.join("blog/_index.md"), "Blog", None, None);
+
+        let sections = discover_sections(content_dir, false).expect("discover failed");
+        let changelog = sections.iter().find(|s| s.name == "changelog").unwrap();
+        let blog = sections.iter().find(|s| s.name == "blog").unwrap();
+
+        assert!(changelog.index.frontmatter.section_feed);
+        assert!(!changelog.has_feed()); // Not part of the site feed
+        assert!(blog.has_feed());
+        assert!(!blog.index.frontmatter.section_feed);
+        assert_eq!(changelog.feed_path(FeedFormat::Atom), "/changelog/feed.xml");
+        assert_eq!(changelog.feed_path(FeedFormat::Rss), "/changelog/rss.xml");
+    }
+
+    #[test]
+    fn test_parse_sitemap_fields() {
+        let dir = create_test_dir();
+        let path = dir.path().join("page.md");
+
+        fs::write(&path, "---\ntitle: Page\n---\n").unwrap();
+        let fm = Content::from_path(&path, ContentKind::Page)
+            .unwrap()
+            .frontmatter;
+        assert!(fm.in_sitemap());
+        assert_eq!(fm.changefreq, None);
+
+        fs::write(
+            &path,
+            "---\ntitle: Page\nnoindex: true\nchangefreq: daily\npriority: 1\n---\n",
+        )
+        .unwrap();
+        let fm = Content::from_path(&path, ContentKind::Page)
+            .unwrap()
+            .frontmatter;
+        assert!(!fm.in_sitemap());
+        assert_eq!(fm.changefreq, Some(ChangeFreq::Daily));
+        assert_eq!(fm.priority, Some(1.0));
+
+        fs::write(&path, "---\ntitle: Page\nchangefreq: often\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Page).unwrap_err();
+        assert!(err.to_string().contains("invalid changefreq"));
+
+        fs::write(&path, "---\ntitle: Page\npriority: 1.5\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Page).unwrap_err();
+        assert!(err.to_string().contains("invalid priority"));
+    }
+
+    #[test]
+    fn test_frontmatter_slug() {
+        let dir = create_test_dir();
+        let path = dir.path().join("2024-01-15-hello.md");
+
+        fs::write(&path, "---\ntitle: Hello\nslug: hello-world\n---\n").unwrap();
+        let content = Content::from_path(&path, ContentKind::Page).unwrap();
+        assert_eq!(content.slug, "hello-world");
+        assert_eq!(
+            content.output_path(dir.path(), UrlStyle::Flat),
+            PathBuf::from("hello-world.html")
+        );
+
+        fs::write(&path, "---\ntitle: Hello\nslug: a/b\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Page).unwrap_err();
+        assert!(err.to_string().contains("invalid slug"));
+    }
+
+    #[test]
+    fn test_frontmatter_toc_levels() {
+        let dir = create_test_dir();
+        let path = dir.path().join("page.md");
+        let nav = NavConfig::default();
+
+        fs::write(&path, "---\ntitle: Page\n---\n").unwrap();
+        let content = Content::from_path(&path, ContentKind::Page).unwrap();
+        assert_eq!(
+            content.frontmatter.toc_levels(&nav),
+            TocLevels { min: 2, max: 6 }
+        );
+
+        fs::write(&path, "---\ntitle: Page\ntoc_max_level: 3\n---\n").unwrap();
+        let content = Content::from_path(&path, ContentKind::Page).unwrap();
+        assert_eq!(
+            content.frontmatter.toc_levels(&nav),
+            TocLevels { min: 2, max: 3 }
+        );
+
+        fs::write(&path, "---\ntitle: Page\ntoc_min_level: 7\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Page).unwrap_err();
+        assert!(err.to_string().contains("invalid toc_min_level 7"));
+    }
+
+    #[test]
+    fn test_section_permalink() {
+        let dir = create_test_dir();
+        let content_dir = dir.path();
+
+        fs::write(content_dir.join("_index.md"), "---\ntitle: Home\n---\n").unwrap();
+        fs::create_dir(content_dir.join("blog")).unwrap();
+        fs::write(
+            content_dir.join("blog/_index.md"),
+            "---\ntitle: Blog\npermalink: /:section/:year/:month/:slug\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            content_dir.join("blog/post.md"),
+            "---\ntitle: Post\ndate: 2024-03-09T10:00:00Z\nslug: first\n---\n",
+        )
+        .unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Pretty)
+            .expect("discover failed");
+        let post = &manifest.section_items[0][0];
+        assert_eq!(post.permalink.as_deref(), Some("/blog/2024/03/first/"));
+        assert_eq!(
+            post.output_path(content_dir, UrlStyle::Pretty),
+            PathBuf::from("blog/2024/03/first/index.html")
+        );
+        assert_eq!(manifest.nav[0].children[0].path, "/blog/2024/03/first/");
+
+        // Date placeholders need a date
+        fs::write(
+            content_dir.join("blog/undated.md"),
+            "---\ntitle: Undated\n---\n",
+        )
+        .unwrap();
+        let err = SiteManifest::discover(content_dir, false, &[], UrlStyle::Pretty).unwrap_err();
+        assert!(err.to_string().contains("missing 'date'"));
+
+        fs::write(
+            content_dir.join("blog/_index.md"),
+            "---\ntitle: Blog\npermalink: /blog/:week/:slug/\n---\n",
+        )
+        .unwrap();
+        let err = SiteManifest::discover(content_dir, false, &[], UrlStyle::Pretty).unwrap_err();
+        assert!(err.to_string().contains("unknown placeholder \":week\""));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let dir = create_test_dir();
+        let path = dir.path().join("post.md");
+
+        fs::write(
+            &path,
+            "---\ntitle: Post\ndate: 2024-01-15T09:30:00+02:00\nupdated: 2024-02-01\n---\n",
+        )
+        .unwrap();
+        let content = Content::from_path(&path, ContentKind::Post).unwrap();
+        let fm = &content.frontmatter;
+        assert_eq!(fm.updated.as_deref(), Some("2024-02-01"));
+        assert_eq!(
+            fm.date_time().unwrap().to_rfc3339(),
+            "2024-01-15T09:30:00+02:00"
+        );
+        assert!(fm.updated_time() > fm.date_time());
+
+        fs::write(&path, "---\ntitle: Post\ndate: last tuesday\n---\n").unwrap();
+        let err = Content::from_path(&path, ContentKind::Post).unwrap_err();
+        assert!(err.to_string().contains("invalid date"));
+    }
 }
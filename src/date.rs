@@ -0,0 +1,327 @@
+//! Minimal calendar date helpers.
+//!
+//! sukr only needs to compare, sort and format frontmatter dates, so this
+//! avoids pulling in a full date/time library.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds in a day.
+const SECS_PER_DAY: u64 = 86_400;
+
+/// A frontmatter date or datetime, with its original UTC offset.
+///
+/// Accepts `YYYY-MM-DD` (midnight UTC) and RFC 3339 datetimes such as
+/// `2024-01-15T09:30:00+02:00`. Ordering compares instants first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    /// Seconds since the Unix epoch
+    timestamp: i64,
+    /// Offset from UTC in minutes
+    offset: i32,
+}
+
+impl DateTime {
+    /// Parse a date or RFC 3339 datetime; a space may replace the `T`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let date = value.get(..10)?;
+        let days = parse_date(date)?;
+
+        let rest = &value[10..];
+        if rest.is_empty() {
+            return Some(Self {
+                timestamp: days * SECS_PER_DAY as i64,
+                offset: 0,
+            });
+        }
+
+        let rest = rest.strip_prefix(['T', 't', ' '])?;
+        let (time, offset) = match rest.find(['Z', 'z', '+', '-']) {
+            Some(i) => (&rest[..i], parse_offset(&rest[i..])?),
+            None => (rest, 0),
+        };
+        let seconds = parse_time(time)?;
+
+        Some(Self {
+            timestamp: days * SECS_PER_DAY as i64 + seconds - i64::from(offset) * 60,
+            offset,
+        })
+    }
+
+    /// Format as RFC 3339 in the original offset (e.g., `2024-01-15T09:30:00+02:00`).
+    pub fn to_rfc3339(self) -> String {
+        let (days, secs) = self.local();
+        let (year, month, day) = civil_from_days(days);
+        let offset = if self.offset == 0 {
+            "Z".to_string()
+        } else {
+            let sign = if self.offset < 0 { '-' } else { '+' };
+            let minutes = self.offset.abs();
+            format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+        };
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+            year,
+            month,
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            offset
+        )
+    }
+
+    /// Format as RFC 2822 for RSS (e.g., `Mon, 15 Jan 2024 09:30:00 +0200`).
+    pub fn to_rfc2822(self) -> String {
+        let (days, secs) = self.local();
+        let (year, month, day) = civil_from_days(days);
+        // 1970-01-01 was a Thursday
+        let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let minutes = self.offset.abs();
+        format!(
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} {}{:02}{:02}",
+            weekday,
+            day,
+            MONTHS[month as usize - 1],
+            year,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            sign,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+
+    /// The calendar date as written, at midnight UTC, dropping the time of
+    /// day and offset (`2024-01-15T23:30:00-05:00` → `2024-01-15`).
+    pub fn date(self) -> Self {
+        let (days, _) = self.local();
+        Self {
+            timestamp: days * SECS_PER_DAY as i64,
+            offset: 0,
+        }
+    }
+
+    /// Local `(days since epoch, seconds into the day)` in the original offset.
+    fn local(self) -> (i64, i64) {
+        let local = self.timestamp + i64::from(self.offset) * 60;
+        (
+            local.div_euclid(SECS_PER_DAY as i64),
+            local.rem_euclid(SECS_PER_DAY as i64),
+        )
+    }
+}
+
+/// Abbreviated weekday names, starting on Sunday.
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Abbreviated month names.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Parse `YYYY-MM-DD` into days since 1970-01-01.
+fn parse_date(date: &str) -> Option<i64> {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year: i64 = parse_digits(&date[..4])?;
+    let month: u32 = parse_digits(&date[5..7])?;
+    let day: u32 = parse_digits(&date[8..10])?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+/// Parse `HH:MM[:SS[.fraction]]` into seconds since midnight.
+fn parse_time(time: &str) -> Option<i64> {
+    let mut parts = time.splitn(3, ':');
+    let hours: i64 = parse_digits(parts.next()?)?;
+    let minutes: i64 = parse_digits(parts.next()?)?;
+    let seconds: i64 = match parts.next() {
+        // Fractional seconds are accepted but dropped
+        Some(s) => parse_digits(s.split_once('.').map_or(s, |(whole, _)| whole))?,
+        None => 0,
+    };
+    (hours < 24 && minutes < 60 && seconds <= 60).then_some(hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Parse `Z` or `±HH:MM` into minutes east of UTC.
+fn parse_offset(offset: &str) -> Option<i32> {
+    if offset.eq_ignore_ascii_case("z") {
+        return Some(0);
+    }
+    let sign = match offset.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = offset[1..].split_once(':')?;
+    let hours: i32 = parse_digits(hours)?;
+    let minutes: i32 = parse_digits(minutes)?;
+    (hours < 24 && minutes < 60).then_some(sign * (hours * 60 + minutes))
+}
+
+/// Parse a fixed-width, ASCII-digit-only number.
+fn parse_digits<T: std::str::FromStr>(digits: &str) -> Option<T> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Today's date in UTC, at midnight.
+pub fn today() -> DateTime {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    DateTime {
+        timestamp: (secs / SECS_PER_DAY * SECS_PER_DAY) as i64,
+        offset: 0,
+    }
+}
+
+/// The `YYYY-MM-DD` prefix of a frontmatter date, ignoring any time part.
+pub fn date_part(date: &str) -> &str {
+    date.get(..10).unwrap_or(date)
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Convert a civil date to days since 1970-01-01.
+///
+/// Howard Hinnant's `days_from_civil` algorithm, the inverse of
+/// [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Convert days since 1970-01-01 to a `(year, month, day)` civil date.
+///
+/// Howard Hinnant's `civil_from_days` algorithm (proleptic Gregorian).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29)); // Leap day
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_today_is_a_date() {
+        let today = today();
+        assert_eq!(today, today.date());
+        assert!(today.to_rfc3339().ends_with("T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_date_drops_time() {
+        let late = DateTime::parse("2024-01-15T23:30:00-05:00").unwrap();
+        assert_eq!(late.date(), DateTime::parse("2024-01-15").unwrap());
+    }
+
+    #[test]
+    fn test_date_part() {
+        assert_eq!(date_part("2026-01-15"), "2026-01-15");
+        assert_eq!(date_part("2026-01-15T10:30:00Z"), "2026-01-15");
+        assert_eq!(date_part("2026"), "2026");
+    }
+
+    #[test]
+    fn test_days_from_civil_roundtrip() {
+        for days in [-1, 0, 19_723, 19_782, 100_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_parse_date_only() {
+        let dt = DateTime::parse("2024-01-15").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-01-15T00:00:00Z");
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let dt = DateTime::parse("2024-01-15T09:30:00+02:00").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-01-15T09:30:00+02:00");
+        let utc = DateTime::parse("2024-01-15T07:30:00Z").unwrap();
+        assert_eq!(dt.timestamp, utc.timestamp, "same instant");
+        assert_eq!(
+            DateTime::parse("2024-01-15 09:30").unwrap().to_rfc3339(),
+            "2024-01-15T09:30:00Z"
+        );
+        assert_eq!(
+            DateTime::parse("2024-03-01T00:15:00.250-05:30")
+                .unwrap()
+                .to_rfc3339(),
+            "2024-03-01T00:15:00-05:30"
+        );
+    }
+
+    #[test]
+    fn test_to_rfc2822() {
+        let dt = DateTime::parse("2024-01-15T09:30:00+02:00").unwrap();
+        assert_eq!(dt.to_rfc2822(), "Mon, 15 Jan 2024 09:30:00 +0200");
+        let dt = DateTime::parse("1969-12-31T23:00:00-05:00").unwrap();
+        assert_eq!(dt.to_rfc2822(), "Wed, 31 Dec 1969 23:00:00 -0500");
+    }
+
+    #[test]
+    fn test_datetime_ordering() {
+        let early = DateTime::parse("2024-01-15T23:00:00+02:00").unwrap();
+        let late = DateTime::parse("2024-01-15T22:00:00Z").unwrap();
+        assert!(early < late);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for value in [
+            "2024",
+            "2024-13-01",
+            "2023-02-29",
+            "2024-01-15T25:00:00Z",
+            "2024-01-15T10:00:00+2",
+            "2024-01-15X10:00",
+            "January 5",
+        ] {
+            assert_eq!(DateTime::parse(value), None, "{value}");
+        }
+    }
+}
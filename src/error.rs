@@ -25,6 +25,14 @@ pub enum Error {
         source: std::io::Error,
     },
 
+    /// Failed to remove a stale output file.
+    #[error("failed to remove {path}: {source}")]
+    RemoveFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Failed to create output directory.
     #[error("failed to create directory {path}: {source}")]
     CreateDir {
@@ -53,9 +61,63 @@ pub enum Error {
         source: tera::Error,
     },
 
+    /// Content uses a taxonomy whose page template is missing.
+    #[error("template '{template}' not found, but content uses taxonomy \"{taxonomy}\"")]
+    TaxonomyTemplate { template: String, taxonomy: String },
+
     /// Failed to bundle CSS.
     #[error("CSS bundle error: {0}")]
     CssBundle(String),
+
+    /// A page alias is invalid or collides with another output.
+    #[error("invalid alias \"{alias}\" in {path}: {message}")]
+    Alias {
+        path: PathBuf,
+        alias: String,
+        message: String,
+    },
+
+    /// An internal link points at a missing page or heading.
+    #[error("broken link \"{link}\" in {path}:{line}: {message}")]
+    BrokenLink {
+        path: PathBuf,
+        line: usize,
+        link: String,
+        message: String,
+    },
+
+    /// Two sources render to the same output file.
+    #[error("{first} and {second} both write {output}")]
+    OutputCollision {
+        output: PathBuf,
+        first: String,
+        second: String,
+    },
+
+    /// `sukr check` found broken references in the output.
+    #[error("found {count} broken reference(s) in {pages} page(s)")]
+    BrokenReferences { count: usize, pages: usize },
+
+    /// Failed to start the development server.
+    #[error("failed to start server on {addr}: {source}")]
+    Serve {
+        addr: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Format the error followed by its full source chain, one cause per line.
+    pub fn report(&self) -> String {
+        let mut out = format!("error: {self}");
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            out.push_str(&format!("\n  caused by: {cause}"));
+            source = cause.source();
+        }
+        out
+    }
 }
 
 /// Result type alias for compiler operations.
@@ -1,4 +1,4 @@
-//! Text escaping utilities for HTML and XML output.
+//! Text escaping utilities for HTML and XML output, and their decoding.
 
 /// Escape HTML special characters for safe embedding in HTML content.
 ///
@@ -57,6 +57,75 @@ pub fn xml_escape(s: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// Decode character references in an HTML attribute value.
+///
+/// Handles the named references produced by HTML escaping (`&amp;`, `&lt;`,
+/// `&gt;`, `&quot;`, `&apos;`) and numeric ones (`&#39;`, `&#x2F;`). Unknown
+/// references are kept as-is.
+pub fn html_unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                entity => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(|n| n.ok())
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Decode `%XX` escapes in a URL path. Returns None on malformed input.
+pub fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            // from_str_radix alone would accept a sign, as in "%+1"
+            let hex = s.get(i + 1..i + 3)?;
+            if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(out).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +145,24 @@ mod tests {
         assert_eq!(buf, "a &lt; b");
     }
 
+    #[test]
+    fn test_html_unescape() {
+        assert_eq!(html_unescape("..&#x2F;blog&#x2F;"), "../blog/");
+        assert_eq!(html_unescape("a?x=1&amp;y=2"), "a?x=1&y=2");
+        assert_eq!(html_unescape("it&#39;s &quot;ok&quot;"), "it's \"ok\"");
+        assert_eq!(html_unescape("R&D &unknown; &"), "R&D &unknown; &");
+        assert_eq!(html_unescape(&html_escape("<a & 'b'>")), "<a & 'b'>");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("/a%20b.html").unwrap(), "/a b.html");
+        assert_eq!(percent_decode("/plain").unwrap(), "/plain");
+        assert!(percent_decode("/bad%2").is_none());
+        assert!(percent_decode("/bad%zz").is_none());
+        assert!(percent_decode("/bad%+1").is_none());
+    }
+
     #[test]
     fn test_xml_escape() {
         assert_eq!(xml_escape("Hello & World"), "Hello &amp; World");
@@ -0,0 +1,253 @@
+//! External link inventory for auditing outbound links.
+//!
+//! Lists every external URL in each page's markdown (links, images and raw
+//! HTML `href`/`src`), flagging insecure `http://` links, `javascript:` URLs
+//! and domains outside the `[external_links]` allow/deny lists. Built from
+//! the sources alone; no URL is fetched.
+
+use crate::config::{ExternalLinksConfig, SiteConfig};
+use crate::content::SiteManifest;
+use crate::html::{is_external, line_at};
+use crate::links::body_start_line;
+use crate::render;
+use serde::Serialize;
+use std::path::Path;
+
+/// External links across the site, serialized as the JSON report.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Number of external links
+    pub links: usize,
+    /// Number of links with at least one flag
+    pub flagged: usize,
+    /// Pages with external links, by source path
+    pub pages: Vec<PageReport>,
+}
+
+/// External links of one page.
+#[derive(Debug, Serialize)]
+pub struct PageReport {
+    /// Markdown source file
+    pub source: String,
+    /// URL path of the page (e.g., "/blog/post.html")
+    pub url: String,
+    pub links: Vec<ExternalLink>,
+}
+
+/// An external URL and what is wrong with it, if anything.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ExternalLink {
+    pub url: String,
+    /// 1-based line in the source file
+    pub line: usize,
+    /// Lowercase host, for URLs that have one
+    pub host: Option<String>,
+    pub flags: Vec<Flag>,
+}
+
+/// Reasons an external link needs attention.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Flag {
+    /// Plain `http://` instead of `https://`
+    Insecure,
+    /// A `javascript:` URL
+    Javascript,
+    /// Host on the deny list
+    Denied,
+    /// Host missing from a non-empty allow list
+    NotAllowed,
+}
+
+/// Build the external link report for every page in the manifest.
+pub fn inventory(manifest: &SiteManifest, content_root: &Path, config: &SiteConfig) -> Report {
+    let site_url = config.site_url();
+    let mut contents: Vec<_> = manifest.content().chain(&manifest.not_found).collect();
+    contents.sort_by(|a, b| a.source_path.cmp(&b.source_path));
+
+    let pages: Vec<PageReport> = contents
+        .into_iter()
+        .filter_map(|content| {
+            let body_line = body_start_line(&content.source_path, &content.body);
+            let links: Vec<_> = render::link_urls(&content.body)
+                .into_iter()
+                .filter(|(url, _)| is_external(url) && !is_site_url(url, &site_url))
+                .map(|(url, offset)| {
+                    let line = body_line + line_at(&content.body, offset) - 1;
+                    ExternalLink::new(url, line, &config.external_links)
+                })
+                .collect();
+            (!links.is_empty()).then(|| PageReport {
+                source: content.source_path.display().to_string(),
+                url: content.url_path(content_root, config.urls.style),
+                links,
+            })
+        })
+        .collect();
+
+    let links = pages.iter().map(|p| p.links.len()).sum();
+    let flagged = pages
+        .iter()
+        .flat_map(|p| &p.links)
+        .filter(|l| !l.flags.is_empty())
+        .count();
+    Report {
+        links,
+        flagged,
+        pages,
+    }
+}
+
+impl ExternalLink {
+    /// Classify `url` against the domain rules.
+    fn new(url: String, line: usize, rules: &ExternalLinksConfig) -> Self {
+        let host = host(&url);
+        let lower = url.trim_start().to_ascii_lowercase();
+        let mut flags = Vec::new();
+
+        if lower.starts_with("http://") {
+            flags.push(Flag::Insecure);
+        }
+        if lower.starts_with("javascript:") {
+            flags.push(Flag::Javascript);
+        }
+        if let Some(host) = &host {
+            if rules.deny.iter().any(|domain| matches_domain(host, domain)) {
+                flags.push(Flag::Denied);
+            } else if !rules.allow.is_empty()
+                && !rules
+                    .allow
+                    .iter()
+                    .any(|domain| matches_domain(host, domain))
+            {
+                flags.push(Flag::NotAllowed);
+            }
+        }
+
+        Self {
+            url,
+            line,
+            host,
+            flags,
+        }
+    }
+}
+
+/// Whether `url` is an absolute URL of the site itself.
+fn is_site_url(url: &str, site_url: &str) -> bool {
+    !site_url.is_empty()
+        && url
+            .strip_prefix(site_url)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?', '#']))
+}
+
+/// Lowercase host of a URL with an authority (`scheme://host` or `//host`).
+fn host(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("//")?;
+    if url.starts_with("//") || url.split_once("://").is_some_and(|(s, _)| !s.contains('/')) {
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+        let host_port = authority.rsplit('@').next().unwrap_or(authority);
+        let host = match host_port.strip_prefix('[') {
+            // IPv6 literal
+            Some(v6) => v6.split(']').next().unwrap_or(v6),
+            None => host_port.split(':').next().unwrap_or(host_port),
+        };
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        (!host.is_empty()).then_some(host)
+    } else {
+        None
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn matches_domain(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_matches('.').to_ascii_lowercase();
+    host == domain
+        || host
+            .strip_suffix(&domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UrlStyle;
+    use std::fs;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("https://Example.COM/a"), Some("example.com".into()));
+        assert_eq!(
+            host("//cdn.example.com:8080?x"),
+            Some("cdn.example.com".into())
+        );
+        assert_eq!(host("http://user@[::1]:80/"), Some("::1".into()));
+        assert_eq!(host("mailto:me@example.com"), None);
+        assert_eq!(host("javascript:go('//x')"), None);
+    }
+
+    #[test]
+    fn test_matches_domain() {
+        assert!(matches_domain("example.com", "example.com"));
+        assert!(matches_domain("docs.example.com", "Example.com"));
+        assert!(!matches_domain("badexample.com", "example.com"));
+        assert!(!matches_domain("example.com.evil.org", "example.com"));
+    }
+
+    #[test]
+    fn test_inventory() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("_index.md"),
+            "---\ntitle: Home\n---\nNo links.\n",
+        );
+        write(
+            &root.join("about.md"),
+            "---\ntitle: About\n---\nSee [docs](https://docs.example.com/guide)\n\
+             and [old](http://example.com/).\n\n\
+             [me](mailto:me@example.org) [x](javascript:alert(1)) [self](https://site.org/a.html)\n\n\
+             <p>\n<img src=\"https://tracker.net/pixel.gif\">\n</p>\n\n\
+             [home](/index.html) [post](@/_index.md)\n",
+        );
+
+        let config: SiteConfig = toml::from_str(
+            "title = \"Site\"\nauthor = \"Author\"\nbase_url = \"https://site.org\"\n\
+             [external_links]\nallow = [\"example.com\"]\ndeny = [\"tracker.net\"]",
+        )
+        .unwrap();
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Flat).unwrap();
+        let report = inventory(&manifest, root, &config);
+
+        assert_eq!(report.pages.len(), 1);
+        assert_eq!(report.pages[0].url, "/about.html");
+        let links: Vec<_> = report.pages[0]
+            .links
+            .iter()
+            .map(|l| (l.url.as_str(), l.line, l.flags.clone()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                ("https://docs.example.com/guide", 4, vec![]),
+                ("http://example.com/", 5, vec![Flag::Insecure]),
+                ("mailto:me@example.org", 7, vec![]),
+                ("javascript:alert(1)", 7, vec![Flag::Javascript]),
+                ("https://tracker.net/pixel.gif", 10, vec![Flag::Denied]),
+            ]
+        );
+        assert_eq!((report.links, report.flagged), (5, 3));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["pages"][0]["links"][1]["flags"][0], "insecure");
+        assert_eq!(
+            json["pages"][0]["links"][2]["host"],
+            serde_json::Value::Null
+        );
+    }
+}
@@ -1,69 +1,236 @@
-//! Atom feed generation.
+//! Feed generation: Atom 1.0, RSS 2.0 and JSON Feed 1.1.
+//!
+//! Every format is rendered from the same [`Feed`] model, so titles, dates,
+//! summaries and content stay consistent across formats.
 
-use crate::config::SiteConfig;
-use crate::content::SiteManifest;
+use crate::config::{FeedContent, FeedFormat, SiteConfig};
+use crate::content::{Content, Section, SiteManifest};
+use crate::date::DateTime;
 use crate::escape::xml_escape;
+use crate::render::Summaries;
+use crate::taxonomy::{Taxonomy, Term};
+use serde::Serialize;
 use std::path::Path;
 
-/// Generate an Atom 1.0 feed from blog posts in the manifest.
-pub fn generate_atom_feed(
-    manifest: &SiteManifest,
-    config: &SiteConfig,
-    content_root: &Path,
-) -> String {
-    let posts = &manifest.posts;
-    let base_url = config.base_url.trim_end_matches('/');
-
-    // Use the most recent post date as feed updated time, or fallback
-    let updated = posts
-        .first()
-        .and_then(|p| p.frontmatter.date.as_ref())
-        .map(|d| format!("{}T00:00:00Z", d))
-        .unwrap_or_else(|| "1970-01-01T00:00:00Z".to_string());
-
-    let mut entries = String::new();
-    for post in posts {
-        // Derive URL from output path (e.g., blog/foo.html → /blog/foo.html)
-        let relative_path = post.output_path(content_root);
-        let post_url = format!("{}/{}", base_url, relative_path.display());
-        let post_date = post
-            .frontmatter
-            .date
-            .as_ref()
-            .map(|d| format!("{}T00:00:00Z", d))
-            .unwrap_or_else(|| "1970-01-01T00:00:00Z".to_string());
-
-        let summary = post
-            .frontmatter
-            .description
-            .as_ref()
-            .map(|s| xml_escape(s))
-            .unwrap_or_default();
+/// Timestamp used when no entry has a date.
+const EPOCH: &str = "1970-01-01T00:00:00Z";
+
+/// A feed, independent of output format.
+pub struct Feed {
+    pub title: String,
+    /// Site, section or term description (None: none is set)
+    pub description: Option<String>,
+    /// Site base URL without trailing slash
+    pub base_url: String,
+    /// Absolute URL of the HTML page the feed mirrors
+    pub home_url: String,
+    /// Stable feed identifier (Atom `<id>`)
+    pub id: String,
+    /// Most recent entry update
+    pub updated: Option<DateTime>,
+    pub author: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// A single feed entry, shared by every output format.
+pub struct FeedEntry {
+    pub title: String,
+    /// Absolute URL, also used as the entry id
+    pub url: String,
+    pub published: Option<DateTime>,
+    /// Last update, falling back to the publication date
+    pub updated: Option<DateTime>,
+    pub summary: Option<Summary>,
+    /// Rendered HTML body (only with `content = "full"`)
+    pub content_html: Option<String>,
+    pub tags: Vec<String>,
+    pub author: String,
+}
+
+/// An entry summary.
+pub enum Summary {
+    /// Plain-text `description` from frontmatter
+    Text(String),
+    /// Rendered HTML summary of the body
+    Html(String),
+}
+
+impl Feed {
+    /// The site-wide feed of feed-section posts.
+    pub fn site(
+        manifest: &SiteManifest,
+        config: &SiteConfig,
+        content_root: &Path,
+        summaries: &Summaries,
+    ) -> Self {
+        Self::new(
+            &config.title,
+            "",
+            &manifest.posts,
+            config,
+            content_root,
+            summaries,
+        )
+    }
+
+    /// The feed of a single section, from its `items` in any order.
+    pub fn section(
+        section: &Section,
+        items: &[Content],
+        config: &SiteConfig,
+        content_root: &Path,
+        summaries: &Summaries,
+    ) -> Self {
+        let mut posts = items.to_vec();
+        posts.sort_by_key(|c| std::cmp::Reverse(c.frontmatter.date_time()));
+
+        let title = format!("{} - {}", config.title, section.index.frontmatter.title);
+        let alternate_path = format!("/{}/index.html", section.name);
+        let feed = Self::new(
+            &title,
+            &alternate_path,
+            &posts,
+            config,
+            content_root,
+            summaries,
+        );
+        match &section.index.frontmatter.description {
+            Some(description) => Self {
+                description: Some(description.clone()),
+                ..feed
+            },
+            None => feed,
+        }
+    }
 
-        entries.push_str(&format!(
-            r#"  <entry>
+    /// The feed of a single taxonomy term (e.g., one tag).
+    pub fn term(
+        taxonomy: &Taxonomy,
+        term: &Term,
+        config: &SiteConfig,
+        content_root: &Path,
+        summaries: &Summaries,
+    ) -> Self {
+        let title = format!("{} - {}", config.title, term.name);
+        Self::new(
+            &title,
+            &taxonomy.term_path(term),
+            &term.items,
+            config,
+            content_root,
+            summaries,
+        )
+    }
+
+    /// Build a feed of `posts` (newest first), limited by `[feed] limit`.
+    ///
+    /// `alternate_path` is the HTML page the feed mirrors and also its id
+    /// (empty for the site root).
+    fn new(
+        title: &str,
+        alternate_path: &str,
+        posts: &[Content],
+        config: &SiteConfig,
+        content_root: &Path,
+        summaries: &Summaries,
+    ) -> Self {
+        let base_url = &config.site_url();
+        let (home_url, id) = if alternate_path.is_empty() {
+            (base_url.to_string(), format!("{}/", base_url))
+        } else {
+            let url = format!("{}{}", base_url, config.urls.style.link(alternate_path));
+            (url.clone(), url)
+        };
+
+        let posts = &posts[..posts.len().min(config.feed.limit.unwrap_or(usize::MAX))];
+        let entries: Vec<_> = posts
+            .iter()
+            .map(|post| FeedEntry::new(post, base_url, config, content_root, summaries))
+            .collect();
+
+        Self {
+            title: title.to_string(),
+            description: config.description.clone(),
+            base_url: base_url.to_string(),
+            home_url,
+            id,
+            updated: entries.iter().filter_map(|e| e.updated).max(),
+            author: config.author.clone(),
+            entries,
+        }
+    }
+
+    /// Render the feed in `format`; `self_path` is the feed's own URL path.
+    pub fn render(&self, format: FeedFormat, self_path: &str) -> String {
+        let self_url = format!("{}{}", self.base_url, self_path);
+        match format {
+            FeedFormat::Atom => self.to_atom(&self_url),
+            FeedFormat::Rss => self.to_rss(&self_url),
+            FeedFormat::Json => self.to_json(&self_url),
+        }
+    }
+
+    /// Render as an Atom 1.0 document.
+    fn to_atom(&self, self_url: &str) -> String {
+        let mut entries = String::new();
+        for entry in &self.entries {
+            let published = entry
+                .published
+                .map(|d| format!("\n    <published>{}</published>", d.to_rfc3339()))
+                .unwrap_or_default();
+            let updated = entry
+                .updated
+                .map_or_else(|| EPOCH.to_string(), DateTime::to_rfc3339);
+            let summary = match &entry.summary {
+                Some(Summary::Text(text)) => format!("<summary>{}</summary>", xml_escape(text)),
+                Some(Summary::Html(html)) => {
+                    format!(r#"<summary type="html">{}</summary>"#, xml_escape(html))
+                }
+                None => "<summary></summary>".to_string(),
+            };
+            let content = entry
+                .content_html
+                .as_ref()
+                .map(|html| {
+                    format!(
+                        "\n    <content type=\"html\">{}</content>",
+                        xml_escape(html)
+                    )
+                })
+                .unwrap_or_default();
+            let categories: String = entry
+                .tags
+                .iter()
+                .map(|tag| format!("\n    <category term=\"{}\"/>", xml_escape(tag)))
+                .collect();
+
+            entries.push_str(&format!(
+                r#"  <entry>
     <title>{}</title>
     <link href="{}" rel="alternate"/>
-    <id>{}</id>
+    <id>{}</id>{}
     <updated>{}</updated>
-    <summary>{}</summary>
+    {}{}{}
   </entry>
 "#,
-            xml_escape(&post.frontmatter.title),
-            post_url,
-            post_url,
-            post_date,
-            summary,
-        ));
-    }
+                xml_escape(&entry.title),
+                entry.url,
+                entry.url,
+                published,
+                updated,
+                summary,
+                content,
+                categories,
+            ));
+        }
 
-    format!(
-        r#"<?xml version="1.0" encoding="utf-8"?>
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
 <feed xmlns="http://www.w3.org/2005/Atom">
   <title>{}</title>
   <link href="{}" rel="alternate"/>
-  <link href="{}/feed.xml" rel="self"/>
-  <id>{}/</id>
+  <link href="{}" rel="self"/>
+  <id>{}</id>
   <updated>{}</updated>
   <author>
     <name>{}</name>
@@ -71,23 +238,471 @@ pub fn generate_atom_feed(
 {}
 </feed>
 "#,
-        xml_escape(&config.title),
-        base_url,
-        base_url,
-        base_url,
-        updated,
-        xml_escape(&config.author),
-        entries,
-    )
+            xml_escape(&self.title),
+            self.home_url,
+            self_url,
+            self.id,
+            self.updated
+                .map_or_else(|| EPOCH.to_string(), DateTime::to_rfc3339),
+            xml_escape(&self.author),
+            entries,
+        )
+    }
+
+    /// Render as an RSS 2.0 document.
+    fn to_rss(&self, self_url: &str) -> String {
+        let mut items = String::new();
+        for entry in &self.entries {
+            let pub_date = entry
+                .published
+                .or(entry.updated)
+                .map(|d| format!("\n      <pubDate>{}</pubDate>", d.to_rfc2822()))
+                .unwrap_or_default();
+            let description = match &entry.summary {
+                Some(Summary::Text(text)) => xml_escape(text),
+                Some(Summary::Html(html)) => xml_escape(html),
+                None => String::new(),
+            };
+            let content = entry
+                .content_html
+                .as_ref()
+                .map(|html| {
+                    format!(
+                        "\n      <content:encoded>{}</content:encoded>",
+                        xml_escape(html)
+                    )
+                })
+                .unwrap_or_default();
+            let categories: String = entry
+                .tags
+                .iter()
+                .map(|tag| format!("\n      <category>{}</category>", xml_escape(tag)))
+                .collect();
+
+            items.push_str(&format!(
+                r#"    <item>
+      <title>{}</title>
+      <link>{}</link>
+      <guid isPermaLink="true">{}</guid>{}
+      <dc:creator>{}</dc:creator>
+      <description>{}</description>{}{}
+    </item>
+"#,
+                xml_escape(&entry.title),
+                entry.url,
+                entry.url,
+                pub_date,
+                xml_escape(&entry.author),
+                description,
+                content,
+                categories,
+            ));
+        }
+
+        let last_build = self
+            .updated
+            .map(|d| format!("\n    <lastBuildDate>{}</lastBuildDate>", d.to_rfc2822()))
+            .unwrap_or_default();
+
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>{}</title>
+    <link>{}</link>
+    <description>{}</description>
+    <atom:link href="{}" rel="self" type="application/rss+xml"/>{}
+{}  </channel>
+</rss>
+"#,
+            xml_escape(&self.title),
+            self.home_url,
+            xml_escape(self.description.as_deref().unwrap_or(&self.title)),
+            self_url,
+            last_build,
+            items,
+        )
+    }
+
+    /// Render as a JSON Feed 1.1 document.
+    fn to_json(&self, self_url: &str) -> String {
+        let feed = JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &self.title,
+            home_page_url: &self.home_url,
+            feed_url: self_url,
+            authors: vec![JsonAuthor { name: &self.author }],
+            items: self.entries.iter().map(JsonItem::from).collect(),
+        };
+        let mut json = serde_json::to_string_pretty(&feed).expect("feed serializes to JSON");
+        json.push('\n');
+        json
+    }
+}
+
+impl FeedEntry {
+    fn new(
+        post: &Content,
+        base_url: &str,
+        config: &SiteConfig,
+        content_root: &Path,
+        summaries: &Summaries,
+    ) -> Self {
+        let fm = &post.frontmatter;
+
+        // Derive URL from output path (e.g., blog/foo.html → /blog/foo.html)
+        let url_path = post.url_path(content_root, config.urls.style);
+
+        // Prefer the plain-text description, then the rendered body summary
+        let summary = match &fm.description {
+            Some(description) => Some(Summary::Text(description.clone())),
+            None => summaries
+                .feed(post)
+                .map(|html| Summary::Html(html.trim_end().to_string())),
+        };
+        let content_html = match config.feed.content {
+            FeedContent::Full => summaries
+                .feed_content(post)
+                .map(|html| html.trim_end().to_string()),
+            FeedContent::Summary => None,
+        };
+
+        Self {
+            title: fm.title.clone(),
+            url: format!("{}{}", base_url, url_path),
+            published: fm.date_time(),
+            updated: fm.updated_time(),
+            summary,
+            content_html,
+            tags: fm.tags.clone(),
+            author: config.author.clone(),
+        }
+    }
+}
+
+/// JSON Feed 1.1 document.
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    authors: Vec<JsonAuthor<'a>>,
+    items: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonAuthor<'a> {
+    name: &'a str,
+}
+
+/// JSON Feed item; at least one of `content_html` and `content_text` is set.
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<String>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+    authors: Vec<JsonAuthor<'a>>,
+}
+
+impl<'a> From<&'a FeedEntry> for JsonItem<'a> {
+    fn from(entry: &'a FeedEntry) -> Self {
+        // JSON Feed summaries are plain text; HTML summaries become content
+        let (summary, summary_html) = match &entry.summary {
+            Some(Summary::Text(text)) => (Some(text.as_str()), None),
+            Some(Summary::Html(html)) => (None, Some(html.as_str())),
+            None => (None, None),
+        };
+        let content_html = entry.content_html.as_deref().or(summary_html);
+        let content_text = match content_html {
+            Some(_) => None,
+            None => Some(summary.unwrap_or("")),
+        };
+
+        Self {
+            id: &entry.url,
+            url: &entry.url,
+            title: &entry.title,
+            summary,
+            content_html,
+            content_text,
+            date_published: entry.published.map(DateTime::to_rfc3339),
+            date_modified: entry.updated.map(DateTime::to_rfc3339),
+            tags: &entry.tags,
+            authors: vec![JsonAuthor {
+                name: &entry.author,
+            }],
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::FragmentCache;
+    use crate::content::ContentKind;
+    use crate::links::LinkResolver;
 
     #[test]
     fn test_xml_escape() {
         assert_eq!(xml_escape("Hello & World"), "Hello &amp; World");
         assert_eq!(xml_escape("<tag>"), "&lt;tag&gt;");
     }
+
+    fn test_config() -> SiteConfig {
+        toml::from_str(
+            r#"
+            title = "Site"
+            author = "Author"
+            base_url = "https://example.com/"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_site_feed_links() {
+        let xml = Feed::new(
+            "Site",
+            "",
+            &[],
+            &test_config(),
+            Path::new(""),
+            &Summaries::default(),
+        )
+        .render(FeedFormat::Atom, "/feed.xml");
+        assert!(xml.contains(r#"<link href="https://example.com" rel="alternate"/>"#));
+        assert!(xml.contains(r#"<link href="https://example.com/feed.xml" rel="self"/>"#));
+        assert!(xml.contains("<id>https://example.com/</id>"));
+    }
+
+    #[test]
+    fn test_term_feed_links() {
+        let xml = Feed::new(
+            "Site - Rust",
+            "/tags/rust/index.html",
+            &[],
+            &test_config(),
+            Path::new(""),
+            &Summaries::default(),
+        )
+        .render(FeedFormat::Atom, "/tags/rust/feed.xml");
+        assert!(xml.contains("<title>Site - Rust</title>"));
+        assert!(xml.contains(
+            r#"<link href="https://example.com/tags/rust/index.html" rel="alternate"/>"#
+        ));
+        assert!(xml.contains(r#"<link href="https://example.com/tags/rust/feed.xml" rel="self"/>"#));
+        assert!(xml.contains("<id>https://example.com/tags/rust/index.html</id>"));
+    }
+
+    #[test]
+    fn test_section_feed() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("changelog");
+        std::fs::create_dir(&changelog).unwrap();
+        std::fs::write(
+            changelog.join("_index.md"),
+            "---\ntitle: Changelog\ndescription: Release notes\nsection_feed: true\n---\n",
+        )
+        .unwrap();
+        for (slug, date) in [("v1", "2024-01-01"), ("v2", "2024-02-01")] {
+            std::fs::write(
+                changelog.join(format!("{slug}.md")),
+                format!("---\ntitle: {slug}\ndate: {date}\n---\nNotes."),
+            )
+            .unwrap();
+        }
+
+        let sections = crate::content::discover_sections(dir.path(), false).unwrap();
+        let section = &sections[0];
+        let mut items = section.collect_items().unwrap();
+        items.sort_by(|a, b| a.slug.cmp(&b.slug)); // Oldest first
+
+        let feed = Feed::section(
+            section,
+            &items,
+            &test_config(),
+            dir.path(),
+            &Summaries::default(),
+        );
+        assert_eq!(feed.title, "Site - Changelog");
+        assert_eq!(feed.home_url, "https://example.com/changelog/index.html");
+        let urls: Vec<_> = feed.entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/changelog/v2.html",
+                "https://example.com/changelog/v1.html"
+            ]
+        );
+
+        let xml = feed.render(FeedFormat::Atom, &section.feed_path(FeedFormat::Atom));
+        assert!(xml.contains(r#"<link href="https://example.com/changelog/feed.xml" rel="self"/>"#));
+        let rss = feed.render(FeedFormat::Rss, &section.feed_path(FeedFormat::Rss));
+        assert!(rss.contains("<description>Release notes</description>"));
+    }
+
+    #[test]
+    fn test_entry_summary_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let described = dir.path().join("described.md");
+        std::fs::write(
+            &described,
+            "---\ntitle: A\ndescription: \"Plain & simple\"\n---\nBody.",
+        )
+        .unwrap();
+        let summarized = dir.path().join("summarized.md");
+        std::fs::write(
+            &summarized,
+            "---\ntitle: B\n---\nLead *text*.\n\n<!-- more -->\n\nRest.",
+        )
+        .unwrap();
+        let posts = [
+            Content::from_path(&described, ContentKind::Post).unwrap(),
+            Content::from_path(&summarized, ContentKind::Post).unwrap(),
+        ];
+
+        let summaries = Summaries::new(
+            &posts,
+            &LinkResolver::default(),
+            &mut FragmentCache::default(),
+        )
+        .unwrap();
+        let xml = Feed::new("Site", "", &posts, &test_config(), dir.path(), &summaries)
+            .render(FeedFormat::Atom, "/feed.xml");
+        assert!(xml.contains("<summary>Plain &amp; simple</summary>"));
+        assert!(xml.contains(
+            r#"<summary type="html">&lt;p&gt;Lead &lt;em&gt;text&lt;/em&gt;.&lt;/p&gt;"#
+        ));
+        assert!(!xml.contains("Rest."));
+    }
+
+    #[test]
+    fn test_entry_dates_content_and_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let write_post = |name: &str, frontmatter: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("---\n{frontmatter}\n---\nBody *text*.")).unwrap();
+            Content::from_path(&path, ContentKind::Post).unwrap()
+        };
+        let posts = [
+            write_post(
+                "new.md",
+                "title: New\ndate: 2024-02-01T09:30:00+02:00\nupdated: 2024-03-01",
+            ),
+            write_post("old.md", "title: Old\ndate: 2024-01-01"),
+        ];
+
+        let mut config = test_config();
+        config.feed.limit = Some(1);
+        config.feed.content = FeedContent::Full;
+        let mut summaries = Summaries::default();
+        summaries
+            .render_feed_contents(
+                &posts,
+                &LinkResolver::default(),
+                &config.nav,
+                &mut FragmentCache::default(),
+            )
+            .unwrap();
+        let xml = Feed::new("Site", "", &posts, &config, dir.path(), &summaries)
+            .render(FeedFormat::Atom, "/feed.xml");
+
+        assert!(xml.contains("<published>2024-02-01T09:30:00+02:00</published>"));
+        assert!(xml.contains("<updated>2024-03-01T00:00:00Z</updated>"));
+        assert!(xml.contains(
+            r#"<content type="html">&lt;p&gt;Body &lt;em&gt;text&lt;/em&gt;.&lt;/p&gt;"#
+        ));
+        assert!(!xml.contains("<title>Old</title>"));
+    }
+
+    #[test]
+    fn test_full_content_broken_link_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        std::fs::write(&path, "---\ntitle: A\n---\nSee [gone](@/missing.md).").unwrap();
+        let posts = [Content::from_path(&path, ContentKind::Post).unwrap()];
+
+        let result = Summaries::default().render_feed_contents(
+            &posts,
+            &LinkResolver::default(),
+            &test_config().nav,
+            &mut FragmentCache::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::BrokenLink { .. })
+        ));
+    }
+
+    fn sample_feed(dir: &Path, content: FeedContent) -> Feed {
+        let path = dir.join("post.md");
+        std::fs::write(
+            &path,
+            "---\ntitle: \"Fish & Chips\"\ndate: 2024-01-15T09:30:00+02:00\ntaxonomies:\n  tags: [food]\n---\nLead *text*.\n\nMore.",
+        )
+        .unwrap();
+        let posts = [Content::from_path(&path, ContentKind::Post).unwrap()];
+        let mut config = test_config();
+        config.feed.content = content;
+        let links = LinkResolver::default();
+        let mut fragments = FragmentCache::default();
+        let mut summaries = Summaries::new(&posts, &links, &mut fragments).unwrap();
+        summaries
+            .render_feed_contents(&posts, &links, &config.nav, &mut fragments)
+            .unwrap();
+        Feed::new("Site", "", &posts, &config, dir, &summaries)
+    }
+
+    #[test]
+    fn test_rss_feed() {
+        let dir = tempfile::tempdir().unwrap();
+        let xml = sample_feed(dir.path(), FeedContent::Full).render(FeedFormat::Rss, "/rss.xml");
+
+        assert!(xml.contains(r#"<rss version="2.0""#));
+        assert!(xml.contains("<channel>\n    <title>Site</title>\n    <link>https://example.com</link>\n    <description>Site</description>"));
+        assert!(xml.contains(
+            r#"<atom:link href="https://example.com/rss.xml" rel="self" type="application/rss+xml"/>"#
+        ));
+        assert!(xml.contains("<title>Fish &amp; Chips</title>"));
+        assert!(xml.contains(r#"<guid isPermaLink="true">https://example.com/post.html</guid>"#));
+        assert!(xml.contains("<pubDate>Mon, 15 Jan 2024 09:30:00 +0200</pubDate>"));
+        assert!(xml.contains(
+            "<description>&lt;p&gt;Lead &lt;em&gt;text&lt;/em&gt;.&lt;/p&gt;</description>"
+        ));
+        assert!(xml.contains("<content:encoded>"));
+        assert!(xml.contains("<category>food</category>"));
+    }
+
+    #[test]
+    fn test_json_feed() {
+        let dir = tempfile::tempdir().unwrap();
+        let json =
+            sample_feed(dir.path(), FeedContent::Summary).render(FeedFormat::Json, "/feed.json");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(value["feed_url"], "https://example.com/feed.json");
+        assert_eq!(value["authors"][0]["name"], "Author");
+
+        let item = &value["items"][0];
+        assert_eq!(item["id"], "https://example.com/post.html");
+        assert_eq!(item["title"], "Fish & Chips");
+        assert_eq!(item["content_html"], "<p>Lead <em>text</em>.</p>");
+        assert_eq!(item["date_published"], "2024-01-15T09:30:00+02:00");
+        assert_eq!(item["tags"][0], "food");
+        assert!(item.get("summary").is_none());
+    }
 }
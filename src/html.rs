@@ -0,0 +1,206 @@
+//! Lightweight HTML scanning for URL attributes and element ids.
+//!
+//! Shared by the output checker (`sukr check`) and the external link
+//! inventory, which also scans raw HTML embedded in markdown.
+
+use crate::escape::html_unescape;
+
+/// Attributes holding URLs.
+const URL_ATTRIBUTES: [&str; 3] = ["href", "src", "xlink:href"];
+
+/// A URL attribute found in an HTML document.
+#[derive(Debug, PartialEq)]
+pub struct Reference {
+    /// Attribute name (e.g., "href")
+    pub attr: String,
+    /// Attribute value, with character references decoded
+    pub value: String,
+    /// Byte offset of the tag's `<` in the scanned document
+    pub offset: usize,
+}
+
+/// URL references and element ids of an HTML document.
+#[derive(Debug, Default)]
+pub struct HtmlScan {
+    pub references: Vec<Reference>,
+    pub ids: Vec<String>,
+}
+
+/// Whether a URL leaves the site: it has a scheme (`https:`, `mailto:`,
+/// `javascript:`, ...) or is protocol-relative (`//host/path`).
+pub fn is_external(url: &str) -> bool {
+    url.starts_with("//")
+        || url
+            .split(['/', '?', '#'])
+            .next()
+            .is_some_and(|first| first.contains(':'))
+}
+
+/// 1-based line of byte `offset` in `text`.
+pub fn line_at(text: &str, offset: usize) -> usize {
+    let end = offset.min(text.len());
+    text.as_bytes()[..end]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// Collect URL attributes and element ids from an HTML document.
+///
+/// A small tokenizer rather than a full parser: it reads tags and their
+/// attributes, skipping comments, doctypes and the raw text of `<script>`
+/// and `<style>` elements.
+pub fn scan(html: &str) -> HtmlScan {
+    let mut scan = HtmlScan::default();
+    let mut pos = 0;
+
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &html[start..];
+
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(html.len(), |end| start + end + 3);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("</") || rest.starts_with("<?") {
+            pos = rest.find('>').map_or(html.len(), |end| start + end + 1);
+            continue;
+        }
+
+        let name_len = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+            .unwrap_or(rest.len() - 1);
+        if name_len == 0 {
+            pos = start + 1; // A lone '<' in text
+            continue;
+        }
+        let name = rest[1..1 + name_len].to_ascii_lowercase();
+
+        let (attributes, tag_len) = parse_attributes(&rest[1 + name_len..]);
+        for (attr, value) in attributes {
+            if attr == "id" {
+                scan.ids.push(value);
+            } else if URL_ATTRIBUTES.contains(&attr.as_str()) {
+                scan.references.push(Reference {
+                    attr,
+                    value,
+                    offset: start,
+                });
+            }
+        }
+        pos = start + 1 + name_len + tag_len;
+
+        // Skip raw text elements
+        if name == "script" || name == "style" {
+            let close = format!("</{name}");
+            pos = html[pos..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or(html.len(), |end| pos + end);
+        }
+    }
+
+    scan
+}
+
+/// Parse the attributes of a tag, starting after its name.
+///
+/// Returns lowercase names with decoded values, and the length consumed up
+/// to and including the closing `>`.
+fn parse_attributes(s: &str) -> (Vec<(String, String)>, usize) {
+    let bytes = s.as_bytes();
+    let mut attributes = Vec::new();
+    let mut i = 0;
+
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return (attributes, i);
+        }
+        if bytes[i] == b'>' {
+            return (attributes, i + 1);
+        }
+
+        let name_start = i;
+        while i < bytes.len()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+            && !bytes[i].is_ascii_whitespace()
+        {
+            i += 1;
+        }
+        let name = s[name_start..i].to_ascii_lowercase();
+
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'=' {
+            attributes.push((name, String::new()));
+            continue;
+        }
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        let value = match bytes.get(i) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let value_start = i + 1;
+                let end = s[value_start..]
+                    .find(quote as char)
+                    .map_or(s.len(), |end| value_start + end);
+                i = (end + 1).min(s.len());
+                &s[value_start..end]
+            }
+            _ => {
+                let value_start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                &s[value_start..i]
+            }
+        };
+        attributes.push((name, html_unescape(value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_html() {
+        let html = "<!DOCTYPE html>\n<a href=\"..&#x2F;blog&#x2F;\" class=x>Blog</a>\n\
+                    <!-- <a href=\"/commented.html\"> -->\n\
+                    <h2 id='intro'>Intro</h2><img src=/logo.png alt=\"a > b\">\n\
+                    <script>let s = '<a href=\"/in-script.html\">';</script>\n\
+                    <pre><code>&lt;a href=&quot;/in-code.html&quot;&gt;</code></pre>";
+
+        let found = scan(html);
+        assert_eq!(found.ids, vec!["intro"]);
+        assert_eq!(
+            found.references,
+            vec![
+                Reference {
+                    attr: "href".to_string(),
+                    value: "../blog/".to_string(),
+                    offset: 16,
+                },
+                Reference {
+                    attr: "src".to_string(),
+                    value: "/logo.png".to_string(),
+                    offset: 123,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_at() {
+        assert_eq!(line_at("a\nb\nc", 0), 1);
+        assert_eq!(line_at("a\nb\nc", 2), 2);
+        assert_eq!(line_at("a\nb\nc", 99), 3);
+    }
+}
@@ -0,0 +1,405 @@
+//! Internal links between content files.
+//!
+//! Markdown links may point at source files instead of output URLs:
+//! `@/blog/post.md` resolves from the content root, and relative paths such as
+//! `../post.md` from the linking file's directory. Both may carry a `#anchor`
+//! naming a heading in the target. Resolved links follow the URL style,
+//! permalinks and base path, so they survive slug and URL changes.
+
+use crate::cache::Fingerprint;
+use crate::config::SiteConfig;
+use crate::content::{Content, SiteManifest};
+use crate::error::Error;
+use crate::render;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Output URLs and heading anchors of every page, by source path.
+#[derive(Debug, Default)]
+pub struct LinkResolver {
+    /// Targets keyed by source path relative to the content root
+    targets: HashMap<PathBuf, Target>,
+    /// Source paths of pages containing internal links
+    linking: HashSet<PathBuf>,
+    /// Fingerprint of every target's URL and anchors
+    targets_hash: u64,
+    content_root: PathBuf,
+    /// Static directory, whose files relative `.md` links may also name
+    static_dir: Option<PathBuf>,
+    /// Base path prepended to links within the site (e.g., "/docs")
+    base_path: String,
+    /// Site root URL prepended to links in feeds (e.g., "https://example.org/docs")
+    site_url: String,
+}
+
+/// A page internal links can point at.
+#[derive(Debug)]
+struct Target {
+    /// URL path of the page (e.g., "/blog/post.html")
+    url: String,
+    /// Heading ids on the page
+    anchors: HashSet<String>,
+}
+
+impl LinkResolver {
+    /// Collect link targets from every page in the manifest.
+    ///
+    /// Files under `static_dir` are copied to the output root, so relative
+    /// `.md` links may name them too.
+    pub fn new(
+        manifest: &SiteManifest,
+        content_root: &Path,
+        static_dir: &Path,
+        config: &SiteConfig,
+    ) -> Self {
+        let mut resolver = Self {
+            content_root: content_root.to_path_buf(),
+            static_dir: Some(static_dir.to_path_buf()),
+            base_path: config.base_path(),
+            site_url: config.site_url(),
+            ..Self::default()
+        };
+
+        // The 404 page can link out but is not a link target
+        for content in manifest.content().chain(&manifest.not_found) {
+            if render::link_dests(&content.body)
+                .iter()
+                .any(|dest| maybe_internal(dest))
+            {
+                resolver.linking.insert(content.source_path.clone());
+            }
+        }
+
+        let mut fingerprint = Fingerprint::new();
+        for content in manifest.content() {
+            let relative = content
+                .source_path
+                .strip_prefix(content_root)
+                .unwrap_or(&content.source_path)
+                .to_path_buf();
+            let target = Target {
+                url: content.url_path(content_root, config.urls.style),
+                anchors: render::heading_ids(&content.body).into_iter().collect(),
+            };
+            fingerprint = fingerprint
+                .str(&relative.to_string_lossy())
+                .str(&target.url);
+            let mut anchors: Vec<_> = target.anchors.iter().collect();
+            anchors.sort();
+            for anchor in anchors {
+                fingerprint = fingerprint.str(anchor);
+            }
+            resolver.targets.insert(relative, target);
+        }
+        resolver.targets_hash = fingerprint.finish();
+
+        resolver
+    }
+
+    /// Fingerprint of the link targets a page depends on, for the build cache.
+    ///
+    /// Pages with internal links depend on every target's URL and anchors,
+    /// so renaming a page or heading re-renders (and re-checks) them; other
+    /// pages depend on nothing.
+    pub fn dependencies(&self, content: &Content) -> u64 {
+        if self.linking.contains(&content.source_path) {
+            self.targets_hash
+        } else {
+            0
+        }
+    }
+
+    /// Links from `content`, as root-relative URLs for its page.
+    pub fn page<'a>(&'a self, content: &'a Content) -> PageLinks<'a> {
+        PageLinks {
+            resolver: self,
+            source: &content.source_path,
+            root: &self.base_path,
+        }
+    }
+
+    /// Links from `content`, as absolute URLs for feeds.
+    pub fn page_absolute<'a>(&'a self, content: &'a Content) -> PageLinks<'a> {
+        PageLinks {
+            resolver: self,
+            source: &content.source_path,
+            root: &self.site_url,
+        }
+    }
+
+    /// Source path of a link target relative to the content root, or None if
+    /// `path` is not an internal link. `source` is the linking file.
+    ///
+    /// Relative `.md` links naming a file in the static directory (e.g., a
+    /// downloadable `notes.md`) are left unchanged; any other target must be
+    /// a page.
+    fn target_path(&self, source: &Path, path: &str) -> Option<Result<PathBuf, String>> {
+        let relative = !path.starts_with("@/");
+        let (base, path) = if let Some(rooted) = path.strip_prefix("@/") {
+            (PathBuf::new(), rooted)
+        } else if is_relative_md(path) {
+            let dir = source
+                .strip_prefix(&self.content_root)
+                .unwrap_or(source)
+                .parent()
+                .unwrap_or(Path::new(""));
+            (dir.to_path_buf(), path)
+        } else {
+            return None;
+        };
+
+        let mut resolved = base;
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        return Some(Err("points outside the content directory".to_string()));
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    return Some(Err(
+                        "expected a path relative to the content directory".to_string()
+                    ));
+                }
+            }
+        }
+        if relative
+            && !self.targets.contains_key(&resolved)
+            && self
+                .static_dir
+                .as_ref()
+                .is_some_and(|dir| dir.join(&resolved).is_file())
+        {
+            return None;
+        }
+        Some(Ok(resolved))
+    }
+}
+
+/// Whether a link destination may be an internal link: an `@/` path or a
+/// relative `.md` path, whether or not it names a page yet.
+fn maybe_internal(dest: &str) -> bool {
+    let path = dest.split('#').next().unwrap_or(dest);
+    path.starts_with("@/") || is_relative_md(path)
+}
+
+/// Whether a link destination is a relative path to a markdown file.
+fn is_relative_md(path: &str) -> bool {
+    !path.starts_with('/') && !path.contains(':') && path.ends_with(".md")
+}
+
+/// Internal link resolution for a single page.
+pub struct PageLinks<'a> {
+    resolver: &'a LinkResolver,
+    /// Source file of the page, for relative links and error messages
+    source: &'a Path,
+    /// Prefix of resolved URLs (base path, or the site URL for feeds)
+    root: &'a str,
+}
+
+impl PageLinks<'_> {
+    /// Resolve a link destination.
+    ///
+    /// Returns `Ok(None)` for destinations that are not internal links
+    /// (external URLs, output paths, plain anchors), and an error message
+    /// when the target page or anchor does not exist.
+    pub fn resolve(&self, dest: &str) -> Result<Option<String>, String> {
+        let (path, anchor) = match dest.split_once('#') {
+            Some((path, anchor)) => (path, Some(anchor)),
+            None => (dest, None),
+        };
+        let Some(target_path) = self.resolver.target_path(self.source, path) else {
+            return Ok(None);
+        };
+        let target_path = target_path?;
+
+        let target = self
+            .resolver
+            .targets
+            .get(&target_path)
+            .ok_or_else(|| format!("no page at {}", target_path.display()))?;
+
+        match anchor {
+            Some(anchor) if !target.anchors.contains(anchor) => {
+                Err(format!("no heading #{anchor} in {}", target_path.display()))
+            }
+            Some(anchor) => Ok(Some(format!("{}{}#{}", self.root, target.url, anchor))),
+            None => Ok(Some(format!("{}{}", self.root, target.url))),
+        }
+    }
+
+    /// Build the error for a broken link at byte `offset` of `body`, the
+    /// markdown body of this page.
+    pub fn broken(&self, body: &str, offset: usize, link: &str, message: String) -> Error {
+        let line_in_body = body[..offset.min(body.len())].matches('\n').count();
+        Error::BrokenLink {
+            path: self.source.to_path_buf(),
+            line: body_start_line(self.source, body) + line_in_body,
+            link: link.to_string(),
+            message,
+        }
+    }
+}
+
+/// 1-based line of the source file where `body` starts, after the frontmatter.
+pub(crate) fn body_start_line(source: &Path, body: &str) -> usize {
+    let raw = fs::read_to_string(source).unwrap_or_default();
+    let start = match raw.rfind(body) {
+        Some(start) if !body.is_empty() => start,
+        _ => 0,
+    };
+    raw[..start].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UrlStyle;
+    use crate::render::TocLevels;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn config(extra: &str) -> SiteConfig {
+        toml::from_str(&format!(
+            "title = \"Site\"\nauthor = \"Author\"\nbase_url = \"https://example.org/docs/\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_internal_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("_index.md"), "---\ntitle: Home\n---\n");
+        write(&root.join("about.md"), "---\ntitle: About\n---\n");
+        write(&root.join("blog/_index.md"), "---\ntitle: Blog\n---\n");
+        write(
+            &root.join("blog/post.md"),
+            "---\ntitle: Post\ndate: 2024-01-01\n---\n## Set Up\n",
+        );
+
+        let static_dir = tempfile::tempdir().unwrap();
+        write(&static_dir.path().join("blog/slides.md"), "# Slides");
+
+        let config = config("[urls]\nstyle = \"pretty\"");
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Pretty).unwrap();
+        let resolver = LinkResolver::new(&manifest, root, static_dir.path(), &config);
+        let post = &manifest.section_items[0][0];
+        let links = resolver.page(post);
+
+        assert_eq!(
+            links.resolve("@/about.md"),
+            Ok(Some("/docs/about/".to_string()))
+        );
+        assert_eq!(
+            links.resolve("../about.md"),
+            Ok(Some("/docs/about/".to_string()))
+        );
+        assert_eq!(
+            links.resolve("_index.md#set-up"),
+            Err("no heading #set-up in blog/_index.md".to_string())
+        );
+        assert_eq!(
+            links.resolve("post.md#set-up"),
+            Ok(Some("/docs/blog/post/#set-up".to_string()))
+        );
+        assert_eq!(
+            resolver.page_absolute(post).resolve("@/blog/_index.md"),
+            Ok(Some("https://example.org/docs/blog/".to_string()))
+        );
+
+        // Not internal links; relative .md links to static files are left
+        // as written
+        assert_eq!(links.resolve("https://example.com/a.md"), Ok(None));
+        assert_eq!(links.resolve("/about.html"), Ok(None));
+        assert_eq!(links.resolve("#set-up"), Ok(None));
+        assert_eq!(links.resolve("slides.md"), Ok(None));
+        assert_eq!(
+            links.resolve("@/blog/slides.md"),
+            Err("no page at blog/slides.md".to_string())
+        );
+
+        assert_eq!(
+            links.resolve("notes.md"),
+            Err("no page at blog/notes.md".to_string())
+        );
+        assert_eq!(
+            links.resolve("../../outside.md"),
+            Err("points outside the content directory".to_string())
+        );
+
+        assert_eq!(
+            links.resolve("@/missing.md"),
+            Err("no page at missing.md".to_string())
+        );
+        assert_eq!(
+            links.resolve("@/../outside.md"),
+            Err("points outside the content directory".to_string())
+        );
+    }
+
+    #[test]
+    fn test_markdown_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("_index.md"), "---\ntitle: Home\n---\n");
+        write(
+            &root.join("guide.md"),
+            "---\ntitle: Guide\n---\n# Guide\n\n## Install\n",
+        );
+        write(
+            &root.join("about.md"),
+            "---\ntitle: About\n---\nRead the [guide](guide.md#install).\n",
+        );
+
+        let config = config("");
+        let manifest = SiteManifest::discover(root, false, &[], UrlStyle::Flat).unwrap();
+        let resolver = LinkResolver::new(&manifest, root, &root.join("static"), &config);
+        let about = manifest.pages.iter().find(|p| p.slug == "about").unwrap();
+        let guide = manifest.pages.iter().find(|p| p.slug == "guide").unwrap();
+
+        let (html, _) = render::markdown_to_html(
+            &about.body,
+            Some(&resolver.page(about)),
+            TocLevels::default(),
+        )
+        .unwrap();
+        assert!(html.contains(r#"<a href="/docs/guide.html#install">guide</a>"#));
+        assert_ne!(resolver.dependencies(about), 0);
+        assert_eq!(resolver.dependencies(guide), 0);
+
+        let body = "Intro.\n\n[Setup](guide.md#setup)\n";
+        let err = render::markdown_to_html(body, Some(&resolver.page(about)), TocLevels::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("broken link \"guide.md#setup\""));
+        assert!(err.to_string().ends_with("no heading #setup in guide.md"));
+    }
+
+    #[test]
+    fn test_broken_link_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("page.md");
+        write(
+            &path,
+            "---\ntitle: Page\n---\nIntro.\n\nSee [x](@/gone.md).\n",
+        );
+        let page = Content::from_path(&path, crate::content::ContentKind::Page).unwrap();
+
+        let resolver = LinkResolver::default();
+        let offset = page.body.find("[x]").unwrap();
+        let err =
+            resolver
+                .page(&page)
+                .broken(&page.body, offset, "@/gone.md", "no page".to_string());
+        assert_eq!(
+            err.to_string(),
+            format!("broken link \"@/gone.md\" in {}:6: no page", path.display())
+        );
+    }
+}
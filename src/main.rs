@@ -2,22 +2,42 @@
 //!
 //! Suckless, Rust, zero JS. Transforms markdown into static HTML.
 
+mod alias;
+mod cache;
+mod check;
 mod config;
 mod content;
 mod css;
+mod date;
 mod error;
 mod escape;
+mod external;
 mod feed;
 mod highlight;
+mod html;
+mod links;
 mod math;
 mod mermaid;
+mod pagination;
+mod parallel;
 mod render;
+mod robots;
+mod serve;
 mod sitemap;
+mod taxonomy;
 mod template_engine;
 
-use crate::content::{Content, ContentKind, DEFAULT_WEIGHT, DEFAULT_WEIGHT_HIGH, NavItem};
+use crate::cache::{BuildCache, Fingerprint, FragmentCache};
+use crate::config::{FeedContent, FeedFormat};
+use crate::content::{Content, NavItem};
 use crate::error::{Error, Result};
-use crate::template_engine::{ContentContext, TemplateEngine};
+use crate::feed::Feed;
+use crate::links::LinkResolver;
+use crate::pagination::Pager;
+use crate::render::Summaries;
+use crate::taxonomy::{Taxonomy, Term};
+use crate::template_engine::{ContentContext, FeedLinkContext, SectionContext, TemplateEngine};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -25,24 +45,68 @@ const USAGE: &str = "\
 sukr - Minimal static site compiler
 
 USAGE:
-    sukr [OPTIONS]
+    sukr [OPTIONS] [COMMAND]
+
+COMMANDS:
+    build                Build the site once (default)
+    serve                Build, serve the output directory and rebuild on change
+    check                Build, then check internal links and fragments in the output
+    links                Print a JSON report of external links in the content
 
 OPTIONS:
     -c, --config <FILE>  Path to site.toml config file (default: ./site.toml)
+    -d, --drafts         Include drafts and future-dated content
+    -j, --jobs <N>       Maximum pages rendered in parallel (default: CPU count)
+    -p, --port <PORT>    Port for `serve` to listen on (default: 8000)
     -h, --help           Print this help message
 ";
 
+/// Subcommand selected on the command line.
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Build the site once and exit.
+    Build,
+    /// Build, serve and rebuild on change.
+    Serve,
+    /// Build, then check the output for broken references.
+    Check,
+    /// Print the external link report.
+    Links,
+}
+
+/// Options controlling a single build.
+#[derive(Debug)]
+struct BuildOptions {
+    /// Maximum number of outputs rendered concurrently
+    jobs: usize,
+    /// Include drafts and future-dated content
+    drafts: bool,
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug)]
+struct Args {
+    command: Command,
+    config_path: PathBuf,
+    port: u16,
+    build: BuildOptions,
+}
+
 fn main() {
     match parse_args() {
-        Ok(Some(config_path)) => {
-            if let Err(e) = run(&config_path) {
-                eprintln!("error: {e}");
+        Ok(Some(args)) => {
+            let result = match args.command {
+                Command::Build => run(&args.config_path, &args.build),
+                Command::Serve => serve::serve(&args.config_path, args.port, || {
+                    run(&args.config_path, &args.build)
+                }),
+                Command::Check => run(&args.config_path, &args.build)
+                    .and_then(|()| check_output(&args.config_path)),
+                Command::Links => report_external_links(&args.config_path, &args.build),
+            };
+            if let Err(e) = result {
                 // Print full error chain
-                let mut source = std::error::Error::source(&e);
-                while let Some(cause) = source {
-                    eprintln!("  caused by: {cause}");
-                    source = std::error::Error::source(cause);
-                }
+                eprintln!("{}", e.report());
                 std::process::exit(1);
             }
         }
@@ -55,9 +119,17 @@ fn main() {
 }
 
 /// Parse command-line arguments. Returns None if --help was requested.
-fn parse_args() -> std::result::Result<Option<PathBuf>, String> {
+fn parse_args() -> std::result::Result<Option<Args>, String> {
     let args: Vec<_> = std::env::args().collect();
-    let mut config_path = PathBuf::from("site.toml");
+    let mut parsed = Args {
+        command: Command::Build,
+        config_path: PathBuf::from("site.toml"),
+        port: serve::DEFAULT_PORT,
+        build: BuildOptions {
+            jobs: parallel::default_jobs(),
+            drafts: false,
+        },
+    };
     let mut i = 1;
 
     while i < args.len() {
@@ -70,19 +142,105 @@ fn parse_args() -> std::result::Result<Option<PathBuf>, String> {
                 if i + 1 >= args.len() {
                     return Err("--config requires an argument".to_string());
                 }
-                config_path = PathBuf::from(&args[i + 1]);
+                parsed.config_path = PathBuf::from(&args[i + 1]);
                 i += 2;
             }
+            "-d" | "--drafts" => {
+                parsed.build.drafts = true;
+                i += 1;
+            }
+            "-j" | "--jobs" => {
+                if i + 1 >= args.len() {
+                    return Err("--jobs requires an argument".to_string());
+                }
+                parsed.build.jobs = args[i + 1]
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid job count: {}", args[i + 1]))?;
+                i += 2;
+            }
+            "-p" | "--port" => {
+                if i + 1 >= args.len() {
+                    return Err("--port requires an argument".to_string());
+                }
+                parsed.port = args[i + 1]
+                    .parse()
+                    .map_err(|_| format!("invalid port: {}", args[i + 1]))?;
+                i += 2;
+            }
+            "build" => {
+                parsed.command = Command::Build;
+                i += 1;
+            }
+            "serve" => {
+                parsed.command = Command::Serve;
+                i += 1;
+            }
+            "check" => {
+                parsed.command = Command::Check;
+                i += 1;
+            }
+            "links" => {
+                parsed.command = Command::Links;
+                i += 1;
+            }
             arg => {
                 return Err(format!("unknown argument: {arg}"));
             }
         }
     }
 
-    Ok(Some(config_path))
+    Ok(Some(parsed))
+}
+
+/// Check the built output for broken internal references, printing each one
+/// grouped by page.
+fn check_output(config_path: &Path) -> Result<()> {
+    let config = config::SiteConfig::load(config_path)?;
+    let base_dir = config_path.parent().unwrap_or(Path::new("."));
+    let output_dir = base_dir.join(&config.paths.output);
+
+    let pages = check::check_site(&output_dir, &config).map_err(|e| Error::ReadFile {
+        path: output_dir.clone(),
+        source: e,
+    })?;
+    if pages.is_empty() {
+        eprintln!("check: no broken references");
+        return Ok(());
+    }
+
+    eprint!("{}", check::format_report(&output_dir, &pages));
+    Err(Error::BrokenReferences {
+        count: pages.iter().map(|p| p.problems.len()).sum(),
+        pages: pages.len(),
+    })
 }
 
-fn run(config_path: &Path) -> Result<()> {
+/// Print the external link report for the site's content as JSON.
+fn report_external_links(config_path: &Path, options: &BuildOptions) -> Result<()> {
+    let config = config::SiteConfig::load(config_path)?;
+    let base_dir = config_path.parent().unwrap_or(Path::new("."));
+    let content_dir = base_dir.join(&config.paths.content);
+    if !content_dir.exists() {
+        return Err(Error::ContentDirNotFound(content_dir.to_path_buf()));
+    }
+
+    let manifest = content::SiteManifest::discover(
+        &content_dir,
+        options.drafts,
+        &config.taxonomies,
+        config.urls.style,
+    )?;
+    let report = external::inventory(&manifest, &content_dir, &config);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("report serializes to JSON")
+    );
+    Ok(())
+}
+
+fn run(config_path: &Path, options: &BuildOptions) -> Result<()> {
     // Load site configuration
     let config = config::SiteConfig::load(config_path)?;
 
@@ -98,228 +256,719 @@ fn run(config_path: &Path) -> Result<()> {
     }
 
     // Load Tera templates
-    let engine = TemplateEngine::new(&template_dir)?;
+    let mut engine = TemplateEngine::new(&template_dir)?;
 
     // Discover all site content in a single pass
-    let manifest = content::SiteManifest::discover(&content_dir)?;
+    let manifest = content::SiteManifest::discover(
+        &content_dir,
+        options.drafts,
+        &config.taxonomies,
+        config.urls.style,
+    )?;
+
+    // Taxonomies in use need their page templates (unused ones are not in
+    // the manifest and need none)
+    if let Some(taxonomy) = manifest.taxonomies.first() {
+        for template in ["taxonomy/list.html", "taxonomy/single.html"] {
+            if !engine.has_template(template) {
+                return Err(Error::TaxonomyTemplate {
+                    template: template.to_string(),
+                    taxonomy: taxonomy.name.clone(),
+                });
+            }
+        }
+    }
+
+    // Internal links (`@/blog/post.md`) resolve against every discovered page
+    let links = LinkResolver::new(&manifest, &content_dir, &static_dir, &config);
+
+    // Summaries for listings and feeds, rendered once per changed page
+    let cache_dir = base_dir.join(cache::CACHE_DIR);
+    let config_hash = cache::config_fingerprint(config_path)?;
+    let mut fragments = FragmentCache::load(&cache_dir, config_hash);
+    let mut summaries = Summaries::new(manifest.content(), &links, &mut fragments)?;
+
+    // The site feed only exists when feed sections have posts
+    if !manifest.posts.is_empty() {
+        engine.set_feeds(FeedLinkContext::all(&config, |format| {
+            format!("/{}", format.file_name())
+        }));
+    }
+
+    // Fingerprint inputs shared by every page; outputs whose inputs are
+    // unchanged since the last build are skipped
+    let mut cache = BuildCache::load(&cache_dir);
+    let site_hash = Fingerprint::new()
+        .hash(cache::site_fingerprint(
+            config_hash,
+            &template_dir,
+            &manifest.nav,
+        )?)
+        .bytes(&[u8::from(!manifest.posts.is_empty())])
+        .finish();
 
     // 0. Copy static assets
-    copy_static_assets(&static_dir, &output_dir)?;
+    copy_static_assets(&static_dir, &output_dir, &mut cache)?;
 
-    // 1. Process all sections
-    for section in &manifest.sections {
-        eprintln!("processing section: {}", section.name);
+    // 1. Plan every output in log order: each section index followed by its
+    //    items, then standalone pages, then the homepage
+    let mut jobs = Vec::new();
+    for (section, items) in manifest.sections.iter().zip(&manifest.section_items) {
+        // Section index depends on every item and subsection it lists
+        let subsections: Vec<_> = manifest.subsections(section).collect();
+        let fingerprint = items
+            .iter()
+            .chain(subsections.iter().map(|sub| &sub.index))
+            .fold(
+                Fingerprint::new().hash(site_hash).content(&section.index),
+                |fp, item| fp.content(item).hash(links.dependencies(item)),
+            )
+            .finish();
+        for pager in section.pages(items.len()) {
+            let output = PathBuf::from(&pager.path[1..]);
+            jobs.push(RenderJob::new(
+                RenderKind::Section {
+                    section,
+                    items,
+                    subsections: subsections.clone(),
+                    pager,
+                },
+                output,
+                fingerprint,
+                &output_dir,
+                &mut cache,
+            ));
+        }
 
-        // Collect and sort items in this section
-        let mut items = section.collect_items()?;
+        for item in items {
+            let fingerprint = Fingerprint::new()
+                .hash(site_hash)
+                .content(item)
+                .hash(links.dependencies(item))
+                .finish();
+            jobs.push(RenderJob::new(
+                RenderKind::Content(item),
+                item.output_path(&content_dir, config.urls.style),
+                fingerprint,
+                &output_dir,
+                &mut cache,
+            ));
+        }
+    }
+    for page in &manifest.pages {
+        let fingerprint = Fingerprint::new()
+            .hash(site_hash)
+            .content(page)
+            .hash(links.dependencies(page))
+            .finish();
+        jobs.push(RenderJob::new(
+            RenderKind::Page(page),
+            page.output_path(&content_dir, config.urls.style),
+            fingerprint,
+            &output_dir,
+            &mut cache,
+        ));
+    }
+    let fingerprint = Fingerprint::new()
+        .hash(site_hash)
+        .content(&manifest.homepage)
+        .hash(links.dependencies(&manifest.homepage))
+        .finish();
+    jobs.push(RenderJob::new(
+        RenderKind::Homepage(&manifest.homepage),
+        PathBuf::from("index.html"),
+        fingerprint,
+        &output_dir,
+        &mut cache,
+    ));
+    if manifest.not_found.is_some() || engine.has_template("404.html") {
+        let fingerprint = manifest
+            .not_found
+            .iter()
+            .fold(Fingerprint::new().hash(site_hash), |fp, page| {
+                fp.content(page).hash(links.dependencies(page))
+            })
+            .finish();
+        jobs.push(RenderJob::new(
+            RenderKind::NotFound(manifest.not_found.as_ref()),
+            PathBuf::from("404.html"),
+            fingerprint,
+            &output_dir,
+            &mut cache,
+        ));
+    }
 
-        // Sort based on section type
-        match section.section_type.as_str() {
-            "blog" => {
-                // Blog: sort by date, newest first
-                items.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
-            }
-            "projects" => {
-                // Projects: sort by weight
-                items.sort_by(|a, b| {
-                    a.frontmatter
-                        .weight
-                        .unwrap_or(DEFAULT_WEIGHT_HIGH)
-                        .cmp(&b.frontmatter.weight.unwrap_or(DEFAULT_WEIGHT_HIGH))
-                });
+    // Feeds depend only on the config and the posts they list
+    let mut feed_jobs = Vec::new();
+    if !manifest.posts.is_empty() {
+        feed_jobs.push(FeedKind::Site(&manifest));
+    }
+    for (section, items) in manifest.sections.iter().zip(&manifest.section_items) {
+        if section.index.frontmatter.section_feed {
+            feed_jobs.push(FeedKind::Section { section, items });
+        }
+    }
+    for taxonomy in manifest.taxonomies.iter().filter(|t| t.feed) {
+        for term in &taxonomy.terms {
+            feed_jobs.push(FeedKind::Term { taxonomy, term });
+        }
+    }
+    let feed_jobs: Vec<_> = feed_jobs
+        .into_iter()
+        .map(|kind| {
+            let fingerprint = feed_fingerprint(&kind, config_hash, &links);
+            FeedJob::new(kind, &config, fingerprint, &output_dir, &mut cache)
+        })
+        .collect();
+
+    // Full post bodies, rendered once and shared by every stale feed
+    if config.feed.content == FeedContent::Full {
+        for job in feed_jobs.iter().filter(|job| !job.is_fresh()) {
+            summaries.render_feed_contents(
+                job.kind.posts(),
+                &links,
+                &config.nav,
+                &mut fragments,
+            )?;
+        }
+    }
+
+    // Every output has a single source, and aliases must not shadow any real page
+    let outputs = page_outputs(&jobs, &manifest)?;
+    let redirects = alias::collect_redirects(&manifest, &content_dir, &outputs, config.urls.style)?;
+
+    // 2. Render concurrently, logging progress in plan order
+    let ctx = RenderContext {
+        engine: &engine,
+        config: &config,
+        nav: &manifest.nav,
+        links: &links,
+        summaries: &summaries,
+        content_dir: &content_dir,
+        output_dir: &output_dir,
+    };
+    parallel::map_ordered(
+        &jobs,
+        options.jobs,
+        |job| render_job(job, &ctx),
+        |result| {
+            if let Ok(log) = result {
+                eprint!("{log}");
             }
-            _ => {
-                // Default: sort by weight then title
-                items.sort_by(|a, b| {
-                    a.frontmatter
-                        .weight
-                        .unwrap_or(DEFAULT_WEIGHT)
-                        .cmp(&b.frontmatter.weight.unwrap_or(DEFAULT_WEIGHT))
-                        .then_with(|| a.frontmatter.title.cmp(&b.frontmatter.title))
-                });
+        },
+    )
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+
+    // 3. Generate the site feed (feed sections only), per-section and
+    //    per-term feeds
+    for job in &feed_jobs {
+        generate_feed(job, &ctx)?;
+    }
+
+    // 4. Generate taxonomy pages
+    generate_taxonomies(&manifest, &ctx, &mut cache)?;
+
+    // 5. Generate sitemap
+    generate_sitemap_file(&output_dir, &manifest, &config, &content_dir, &mut cache)?;
+
+    // 6. Generate robots.txt (only with a [robots] config)
+    if let Some(robots) = &config.robots {
+        generate_robots_file(
+            &output_dir,
+            &manifest,
+            robots,
+            &config,
+            &content_dir,
+            &mut cache,
+        )?;
+    }
+
+    // 7. Generate alias redirects
+    if !redirects.is_empty() {
+        generate_redirects(&output_dir, &redirects, &config, &mut cache)?;
+    }
+
+    // 8. Remove outputs the previous build wrote but this one did not
+    for path in cache.remove_orphans(&output_dir)? {
+        eprintln!("removing: {}", path.display());
+    }
+    cache.save()?;
+    fragments.save()?;
+
+    eprintln!("done!");
+    Ok(())
+}
+
+/// What a render job produces.
+enum RenderKind<'a> {
+    /// One page of a section index listing its items
+    Section {
+        section: &'a content::Section,
+        /// Every item in the section, sorted
+        items: &'a [Content],
+        /// Direct subsections, listed on every page
+        subsections: Vec<&'a content::Section>,
+        pager: Pager,
+    },
+    /// Item inside a section (post, project, etc.)
+    Content(&'a Content),
+    /// Standalone top-level page
+    Page(&'a Content),
+    /// Site homepage (content/_index.md)
+    Homepage(&'a Content),
+    /// 404 page (content/404.md, or the 404.html template alone)
+    NotFound(Option<&'a Content>),
+}
+
+impl RenderKind<'_> {
+    /// Human-readable source of the output, for error messages.
+    fn source(&self) -> String {
+        match self {
+            Self::Section { section, .. } => section.index.source_path.display().to_string(),
+            Self::Content(c) | Self::Page(c) | Self::Homepage(c) | Self::NotFound(Some(c)) => {
+                c.source_path.display().to_string()
             }
+            Self::NotFound(None) => "the 404.html template".to_string(),
         }
+    }
+}
+
+/// A single output file to render.
+struct RenderJob<'a> {
+    kind: RenderKind<'a>,
+    /// Output path relative to the output directory
+    output: PathBuf,
+    /// Whether the build cache says the existing output is up to date
+    fresh: bool,
+}
+
+impl<'a> RenderJob<'a> {
+    /// Plan a job, consulting and updating the build cache.
+    fn new(
+        kind: RenderKind<'a>,
+        output: PathBuf,
+        fingerprint: u64,
+        output_dir: &Path,
+        cache: &mut BuildCache,
+    ) -> Self {
+        let fresh = !cache.needs_render(output_dir, &output, fingerprint);
+        Self {
+            kind,
+            output,
+            fresh,
+        }
+    }
+}
+
+/// Shared, read-only state needed by every render job.
+struct RenderContext<'a> {
+    engine: &'a TemplateEngine,
+    config: &'a config::SiteConfig,
+    nav: &'a [NavItem],
+    links: &'a LinkResolver,
+    summaries: &'a Summaries,
+    content_dir: &'a Path,
+    output_dir: &'a Path,
+}
 
-        // Render individual content pages for all sections
-        for item in &items {
-            eprintln!("  processing: {}", item.slug);
-            let (html_body, anchors) = render::markdown_to_html(&item.body);
-            let page_path = format!("/{}", item.output_path(&content_dir).display());
-            let html = engine.render_content(
-                item,
-                &html_body,
-                &page_path,
-                &config,
-                &manifest.nav,
-                &anchors,
+/// Render and write a single output. Returns its progress log lines.
+fn render_job(job: &RenderJob, ctx: &RenderContext) -> Result<String> {
+    let page_path = format!("/{}", job.output.display());
+
+    let (mut log, html) = match &job.kind {
+        RenderKind::Section {
+            section,
+            items,
+            subsections,
+            pager,
+        } => {
+            // Later pages log under the section header of the first
+            let log = if pager.number == 1 {
+                format!("processing section: {}\n", section.name)
+            } else {
+                String::new()
+            };
+            if job.fresh {
+                return Ok(format!("{log}  unchanged: {}\n", job.output.display()));
+            }
+            let item_contexts: Vec<_> = items[pager.range.clone()]
+                .iter()
+                .map(|c| {
+                    ContentContext::from_content(c, ctx.content_dir, ctx.config, ctx.summaries)
+                })
+                .collect();
+            let subsection_contexts: Vec<_> = subsections
+                .iter()
+                .map(|s| SectionContext::new(s, ctx.config))
+                .collect();
+            let html = ctx.engine.render_section(
+                section,
+                &item_contexts,
+                &subsection_contexts,
+                pager,
+                ctx.config,
+                ctx.nav,
+            )?;
+            (log, html)
+        }
+        RenderKind::Content(item) => {
+            if job.fresh {
+                return Ok(format!("  unchanged: {}\n", item.slug));
+            }
+            let (html_body, anchors) = render::markdown_to_html(
+                &item.body,
+                Some(&ctx.links.page(item)),
+                item.frontmatter.toc_levels(&ctx.config.nav),
+            )?;
+            let html = ctx
+                .engine
+                .render_content(item, &html_body, &page_path, ctx.config, ctx.nav, &anchors)?;
+            (format!("  processing: {}\n", item.slug), html)
+        }
+        RenderKind::Page(page) => {
+            if job.fresh {
+                return Ok(format!("unchanged: {}\n", page.source_path.display()));
+            }
+            let (html_body, anchors) = render::markdown_to_html(
+                &page.body,
+                Some(&ctx.links.page(page)),
+                page.frontmatter.toc_levels(&ctx.config.nav),
+            )?;
+            let html = ctx
+                .engine
+                .render_page(page, &html_body, &page_path, ctx.config, ctx.nav, &anchors)?;
+            (
+                format!("processing: {}\n", page.source_path.display()),
+                html,
+            )
+        }
+        RenderKind::Homepage(homepage) => {
+            if job.fresh {
+                return Ok("unchanged: homepage\n".to_string());
+            }
+            let (html_body, anchors) = render::markdown_to_html(
+                &homepage.body,
+                Some(&ctx.links.page(homepage)),
+                homepage.frontmatter.toc_levels(&ctx.config.nav),
             )?;
-            write_output(&output_dir, &content_dir, item, html)?;
+            let html = ctx.engine.render_page(
+                homepage, &html_body, &page_path, ctx.config, ctx.nav, &anchors,
+            )?;
+            ("generating: homepage\n".to_string(), html)
+        }
+        RenderKind::NotFound(page) => {
+            if job.fresh {
+                return Ok("unchanged: 404 page\n".to_string());
+            }
+            let (html_body, anchors) = page
+                .map(|page| {
+                    render::markdown_to_html(
+                        &page.body,
+                        Some(&ctx.links.page(page)),
+                        page.frontmatter.toc_levels(&ctx.config.nav),
+                    )
+                })
+                .transpose()?
+                .unwrap_or_default();
+            let html = ctx
+                .engine
+                .render_not_found(*page, &html_body, ctx.config, ctx.nav, &anchors)?;
+            ("generating: 404 page\n".to_string(), html)
         }
+    };
+
+    let out_path = write_output(ctx.output_dir, &job.output, html)?;
+    log.push_str(&format!("  → {}\n", out_path.display()));
+    Ok(log)
+}
+
+/// Which feed a feed job produces.
+enum FeedKind<'a> {
+    /// Site-wide feed of feed-section posts
+    Site(&'a content::SiteManifest),
+    /// Own feed of a section with `section_feed: true`
+    Section {
+        section: &'a content::Section,
+        items: &'a [Content],
+    },
+    /// Feed of a single taxonomy term
+    Term {
+        taxonomy: &'a Taxonomy,
+        term: &'a Term,
+    },
+}
+
+impl<'a> FeedKind<'a> {
+    /// Every post the feed may list, before `[feed] limit`.
+    fn posts(&self) -> &'a [Content] {
+        match self {
+            Self::Site(manifest) => &manifest.posts,
+            Self::Section { items, .. } => items,
+            Self::Term { term, .. } => &term.items,
+        }
+    }
+
+    /// Output path of the feed in `format`, relative to the output directory.
+    fn output(&self, format: FeedFormat) -> PathBuf {
+        match self {
+            Self::Site(_) => PathBuf::from(format.file_name()),
+            Self::Section { section, .. } => PathBuf::from(&section.feed_path(format)[1..]),
+            Self::Term { taxonomy, term } => {
+                PathBuf::from(&taxonomy.term_feed_path(term, format)[1..])
+            }
+        }
+    }
+}
+
+/// A feed to generate in every configured format.
+struct FeedJob<'a> {
+    kind: FeedKind<'a>,
+    /// Each format, its output path and whether the existing output is up to date
+    outputs: Vec<(FeedFormat, PathBuf, bool)>,
+}
 
-        // Render section index
-        let page_path = format!("/{}/index.html", section.name);
-        let item_contexts: Vec<_> = items
+impl<'a> FeedJob<'a> {
+    /// Plan a feed, consulting and updating the build cache.
+    fn new(
+        kind: FeedKind<'a>,
+        config: &config::SiteConfig,
+        fingerprint: u64,
+        output_dir: &Path,
+        cache: &mut BuildCache,
+    ) -> Self {
+        let outputs = config
+            .feed
+            .formats
             .iter()
-            .map(|c| ContentContext::from_content(c, &content_dir, &config))
+            .map(|&format| {
+                let output = kind.output(format);
+                let fresh = !cache.needs_render(output_dir, &output, fingerprint);
+                (format, output, fresh)
+            })
             .collect();
-        let html = engine.render_section(
-            &section.index,
-            &section.section_type,
-            &item_contexts,
-            &page_path,
-            &config,
-            &manifest.nav,
-        );
+        Self { kind, outputs }
+    }
 
-        let out_path = output_dir.join(&section.name).join("index.html");
-        fs::create_dir_all(out_path.parent().unwrap()).map_err(|e| Error::CreateDir {
-            path: out_path.parent().unwrap().to_path_buf(),
-            source: e,
-        })?;
-        fs::write(&out_path, html?).map_err(|e| Error::WriteFile {
-            path: out_path.clone(),
-            source: e,
-        })?;
-        eprintln!("  → {}", out_path.display());
+    /// Whether every format of the feed is up to date.
+    fn is_fresh(&self) -> bool {
+        self.outputs.iter().all(|(_, _, fresh)| *fresh)
     }
+}
 
-    // 2. Generate Atom feed (blog posts only)
-    if !manifest.posts.is_empty() {
-        generate_feed(&output_dir, &manifest, &config, &content_dir)?;
+/// Fingerprint a feed's inputs: the config, the section index for section
+/// feeds, and every post it may list with its link targets.
+fn feed_fingerprint(kind: &FeedKind, config_hash: u64, links: &LinkResolver) -> u64 {
+    let fp = Fingerprint::new().hash(config_hash);
+    let fp = match kind {
+        FeedKind::Section { section, .. } => fp.content(&section.index),
+        FeedKind::Site(_) | FeedKind::Term { .. } => fp,
+    };
+    kind.posts()
+        .iter()
+        .fold(fp, |fp, post| {
+            fp.str(&post.source_path.to_string_lossy())
+                .content(post)
+                .hash(links.dependencies(post))
+        })
+        .finish()
+}
+
+/// Generate a feed in every configured format whose output is out of date
+fn generate_feed(job: &FeedJob, ctx: &RenderContext) -> Result<()> {
+    match &job.kind {
+        FeedKind::Site(_) => eprintln!("generating: site feed"),
+        FeedKind::Section { section, .. } => eprintln!("generating: feeds for {}", section.name),
+        FeedKind::Term { taxonomy, term } => {
+            eprintln!("generating: feeds for {} \"{}\"", taxonomy.name, term.name)
+        }
     }
 
-    // 3. Process standalone pages
-    process_pages(&content_dir, &output_dir, &config, &manifest.nav, &engine)?;
+    if job.is_fresh() {
+        for (_, output, _) in &job.outputs {
+            eprintln!("  unchanged: {}", output.display());
+        }
+        return Ok(());
+    }
 
-    // 4. Generate homepage
-    generate_homepage(&manifest, &output_dir, &config, &engine)?;
+    let feed = match &job.kind {
+        FeedKind::Site(manifest) => {
+            Feed::site(manifest, ctx.config, ctx.content_dir, ctx.summaries)
+        }
+        FeedKind::Section { section, items } => {
+            Feed::section(section, items, ctx.config, ctx.content_dir, ctx.summaries)
+        }
+        FeedKind::Term { taxonomy, term } => {
+            Feed::term(taxonomy, term, ctx.config, ctx.content_dir, ctx.summaries)
+        }
+    };
+    for (format, output, fresh) in &job.outputs {
+        if *fresh {
+            eprintln!("  unchanged: {}", output.display());
+            continue;
+        }
+        let xml = feed.render(*format, &format!("/{}", output.display()));
+        let out_path = write_output(ctx.output_dir, output, xml)?;
+        eprintln!("  → {}", out_path.display());
+    }
+    Ok(())
+}
 
-    // 5. Generate sitemap
-    generate_sitemap_file(&output_dir, &manifest, &config, &content_dir)?;
+/// Generate taxonomy index pages and paginated term listings
+fn generate_taxonomies(
+    manifest: &content::SiteManifest,
+    ctx: &RenderContext,
+    cache: &mut BuildCache,
+) -> Result<()> {
+    for taxonomy in &manifest.taxonomies {
+        eprintln!("processing taxonomy: {}", taxonomy.name);
 
-    eprintln!("done!");
+        let page_path = taxonomy.path();
+        let html = ctx
+            .engine
+            .render_taxonomy_list(taxonomy, &page_path, ctx.config, ctx.nav)?;
+        let out_path = write_output(ctx.output_dir, Path::new(&page_path[1..]), html)?;
+        cache.record(Path::new(&page_path[1..]));
+        eprintln!("  → {}", out_path.display());
+
+        for term in &taxonomy.terms {
+            let items: Vec<_> = term
+                .items
+                .iter()
+                .map(|c| {
+                    ContentContext::from_content(c, ctx.content_dir, ctx.config, ctx.summaries)
+                })
+                .collect();
+            for pager in taxonomy.term_pages(term) {
+                let html = ctx.engine.render_taxonomy_term(
+                    taxonomy,
+                    term,
+                    &items[pager.range.clone()],
+                    &pager,
+                    ctx.config,
+                    ctx.nav,
+                )?;
+                let out_path = write_output(ctx.output_dir, Path::new(&pager.path[1..]), html)?;
+                cache.record(Path::new(&pager.path[1..]));
+                eprintln!("  → {}", out_path.display());
+            }
+        }
+    }
     Ok(())
 }
 
-/// Generate the Atom feed
-fn generate_feed(
+/// Generate the XML sitemap (split behind a sitemap index when large)
+fn generate_sitemap_file(
     output_dir: &Path,
     manifest: &content::SiteManifest,
     config: &config::SiteConfig,
     content_dir: &Path,
+    cache: &mut BuildCache,
 ) -> Result<()> {
-    let out_path = output_dir.join("feed.xml");
-    eprintln!("generating: {}", out_path.display());
-
-    let feed_xml = feed::generate_atom_feed(manifest, config, content_dir);
-
-    fs::write(&out_path, feed_xml).map_err(|e| Error::WriteFile {
-        path: out_path.clone(),
-        source: e,
-    })?;
+    eprintln!("generating: {}", output_dir.join("sitemap.xml").display());
 
-    eprintln!("  → {}", out_path.display());
+    for (name, xml) in sitemap::generate_sitemap(manifest, config, content_dir) {
+        let out_path = output_dir.join(&name);
+        fs::write(&out_path, xml).map_err(|e| Error::WriteFile {
+            path: out_path.clone(),
+            source: e,
+        })?;
+        cache.record(Path::new(&name));
+        eprintln!("  → {}", out_path.display());
+    }
     Ok(())
 }
 
-/// Generate the XML sitemap
-fn generate_sitemap_file(
+/// Generate robots.txt
+fn generate_robots_file(
     output_dir: &Path,
     manifest: &content::SiteManifest,
+    robots: &config::RobotsConfig,
     config: &config::SiteConfig,
     content_dir: &Path,
+    cache: &mut BuildCache,
 ) -> Result<()> {
-    let out_path = output_dir.join("sitemap.xml");
+    let out_path = output_dir.join("robots.txt");
     eprintln!("generating: {}", out_path.display());
 
-    let sitemap_xml = sitemap::generate_sitemap(manifest, config, content_dir);
+    let robots_txt = robots::generate_robots(manifest, robots, config, content_dir);
 
-    fs::write(&out_path, sitemap_xml).map_err(|e| Error::WriteFile {
+    fs::write(&out_path, robots_txt).map_err(|e| Error::WriteFile {
         path: out_path.clone(),
         source: e,
     })?;
+    cache.record(Path::new("robots.txt"));
 
     eprintln!("  → {}", out_path.display());
     Ok(())
 }
 
-/// Process standalone pages in content/ (top-level .md files excluding _index.md)
-fn process_pages(
-    content_dir: &Path,
-    output_dir: &Path,
-    config: &config::SiteConfig,
-    nav: &[NavItem],
-    engine: &TemplateEngine,
-) -> Result<()> {
-    // Dynamically discover top-level .md files (except _index.md)
-    let entries = fs::read_dir(content_dir).map_err(|e| Error::ReadFile {
-        path: content_dir.to_path_buf(),
-        source: e,
-    })?;
+/// Output paths of every HTML page: rendered content plus taxonomy listings.
+///
+/// Fails when two sources write the same file, e.g. `blog.md` and
+/// `blog/_index.md` with pretty URLs.
+fn page_outputs(jobs: &[RenderJob], manifest: &content::SiteManifest) -> Result<HashSet<PathBuf>> {
+    let mut sources: HashMap<PathBuf, String> = HashMap::new();
+    let mut add = |output: PathBuf, source: String| match sources.get(&output) {
+        Some(first) => Err(Error::OutputCollision {
+            output,
+            first: first.clone(),
+            second: source,
+        }),
+        None => {
+            sources.insert(output, source);
+            Ok(())
+        }
+    };
 
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if path.is_file()
-            && path.extension().is_some_and(|ext| ext == "md")
-            && path.file_name().is_some_and(|n| n != "_index.md")
-        {
-            eprintln!("processing: {}", path.display());
-
-            let content = Content::from_path(&path, ContentKind::Page)?;
-            let (html_body, anchors) = render::markdown_to_html(&content.body);
-            let page_path = format!("/{}", content.output_path(content_dir).display());
-            let html =
-                engine.render_page(&content, &html_body, &page_path, config, nav, &anchors)?;
-
-            write_output(output_dir, content_dir, &content, html)?;
+    for job in jobs {
+        add(job.output.clone(), job.kind.source())?;
+    }
+    for taxonomy in &manifest.taxonomies {
+        let source = format!("taxonomy \"{}\"", taxonomy.name);
+        add(PathBuf::from(&taxonomy.path()[1..]), source.clone())?;
+        for term in &taxonomy.terms {
+            for pager in taxonomy.term_pages(term) {
+                add(PathBuf::from(&pager.path[1..]), source.clone())?;
+            }
         }
     }
-    Ok(())
+    Ok(sources.into_keys().collect())
 }
 
-/// Generate the homepage from manifest.homepage
-fn generate_homepage(
-    manifest: &content::SiteManifest,
+/// Generate a redirect page per alias, plus a Netlify `_redirects` file if enabled
+fn generate_redirects(
     output_dir: &Path,
+    redirects: &[alias::Redirect],
     config: &config::SiteConfig,
-    engine: &TemplateEngine,
+    cache: &mut BuildCache,
 ) -> Result<()> {
-    eprintln!("generating: homepage");
-
-    let (html_body, anchors) = render::markdown_to_html(&manifest.homepage.body);
-    let html = engine.render_page(
-        &manifest.homepage,
-        &html_body,
-        "/index.html",
-        config,
-        &manifest.nav,
-        &anchors,
-    )?;
-
-    let out_path = output_dir.join("index.html");
+    eprintln!("generating: alias redirects");
 
-    fs::create_dir_all(output_dir).map_err(|e| Error::CreateDir {
-        path: output_dir.to_path_buf(),
-        source: e,
-    })?;
-
-    fs::write(&out_path, html).map_err(|e| Error::WriteFile {
-        path: out_path.clone(),
-        source: e,
-    })?;
+    let base_url = config.site_url();
+    for redirect in redirects {
+        let html = alias::redirect_html(&format!("{}{}", base_url, redirect.to));
+        let out_path = write_output(output_dir, &redirect.output, html)?;
+        cache.record(&redirect.output);
+        eprintln!("  → {}", out_path.display());
+    }
 
-    eprintln!("  → {}", out_path.display());
+    if config.redirects.netlify {
+        let out_path = write_output(
+            output_dir,
+            Path::new("_redirects"),
+            alias::netlify_redirects(redirects, &config.base_path()),
+        )?;
+        cache.record(Path::new("_redirects"));
+        eprintln!("  → {}", out_path.display());
+    }
     Ok(())
 }
 
-/// Write a content item to its output path
-fn write_output(
-    output_dir: &Path,
-    content_dir: &Path,
-    content: &Content,
-    html: String,
-) -> Result<()> {
-    let out_path = output_dir.join(content.output_path(content_dir));
+/// Write rendered output to its path relative to the output directory.
+/// Returns the full output path.
+fn write_output(output_dir: &Path, relative: &Path, html: String) -> Result<PathBuf> {
+    let out_path = output_dir.join(relative);
     let out_dir = out_path.parent().unwrap();
 
     fs::create_dir_all(out_dir).map_err(|e| Error::CreateDir {
@@ -332,13 +981,12 @@ fn write_output(
         source: e,
     })?;
 
-    eprintln!("  → {}", out_path.display());
-    Ok(())
+    Ok(out_path)
 }
 
 /// Copy static assets (CSS, images, etc.) to output directory.
 /// CSS files are minified before writing.
-fn copy_static_assets(static_dir: &Path, output_dir: &Path) -> Result<()> {
+fn copy_static_assets(static_dir: &Path, output_dir: &Path, cache: &mut BuildCache) -> Result<()> {
     use crate::css::bundle_css;
 
     if !static_dir.exists() {
@@ -358,6 +1006,7 @@ fn copy_static_assets(static_dir: &Path, output_dir: &Path) -> Result<()> {
         let src = entry.path();
         let relative = src.strip_prefix(static_dir).unwrap();
         let dest = output_dir.join(relative);
+        cache.record(relative);
 
         if let Some(parent) = dest.parent() {
             fs::create_dir_all(parent).map_err(|e| Error::CreateDir {
@@ -0,0 +1,115 @@
+//! Splitting listings into numbered pages.
+//!
+//! The first page of a listing rooted at `/blog` lives at `/blog/index.html`;
+//! page `n` lives at `/blog/page/n/index.html`.
+
+use std::ops::Range;
+
+/// One page of a paginated listing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pager {
+    /// 1-based page number
+    pub number: usize,
+    /// Total number of pages in the listing
+    pub total_pages: usize,
+    /// Total number of items across all pages
+    pub total_items: usize,
+    /// URL path of this page
+    pub path: String,
+    /// URL path of the first page
+    pub first_path: String,
+    /// URL path of the last page
+    pub last_path: String,
+    /// URL path of the previous page, if any
+    pub prev_path: Option<String>,
+    /// URL path of the next page, if any
+    pub next_path: Option<String>,
+    /// Indices of the listing's items shown on this page
+    pub range: Range<usize>,
+}
+
+/// URL path of page `number` of the listing rooted at `dir` (e.g., "/blog").
+pub fn page_path(dir: &str, number: usize) -> String {
+    if number <= 1 {
+        format!("{}/index.html", dir)
+    } else {
+        format!("{}/page/{}/index.html", dir, number)
+    }
+}
+
+/// Split `total_items` into pages of `per_page` items.
+///
+/// `None` or `Some(0)` disables pagination: a single page holds every item.
+/// An empty listing still produces one (empty) page.
+pub fn paginate(dir: &str, total_items: usize, per_page: Option<usize>) -> Vec<Pager> {
+    let per_page = per_page.filter(|&n| n > 0).unwrap_or(total_items.max(1));
+    let total_pages = total_items.div_ceil(per_page).max(1);
+
+    (1..=total_pages)
+        .map(|number| {
+            let start = (number - 1) * per_page;
+            let end = (start + per_page).min(total_items);
+            Pager {
+                number,
+                total_pages,
+                total_items,
+                path: page_path(dir, number),
+                first_path: page_path(dir, 1),
+                last_path: page_path(dir, total_pages),
+                prev_path: (number > 1).then(|| page_path(dir, number - 1)),
+                next_path: (number < total_pages).then(|| page_path(dir, number + 1)),
+                range: start..end,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_path() {
+        assert_eq!(page_path("/blog", 1), "/blog/index.html");
+        assert_eq!(page_path("/blog", 3), "/blog/page/3/index.html");
+    }
+
+    #[test]
+    fn test_paginate_splits_items() {
+        let pages = paginate("/blog", 5, Some(2));
+        assert_eq!(pages.len(), 3);
+
+        assert_eq!(pages[0].range, 0..2);
+        assert_eq!(pages[0].prev_path, None);
+        assert_eq!(
+            pages[0].next_path.as_deref(),
+            Some("/blog/page/2/index.html")
+        );
+
+        assert_eq!(pages[1].path, "/blog/page/2/index.html");
+        assert_eq!(pages[1].prev_path.as_deref(), Some("/blog/index.html"));
+
+        assert_eq!(pages[2].range, 4..5);
+        assert_eq!(pages[2].next_path, None);
+        assert_eq!(pages[2].first_path, "/blog/index.html");
+        assert_eq!(pages[0].last_path, "/blog/page/3/index.html");
+        assert!(pages.iter().all(|p| p.total_pages == 3));
+    }
+
+    #[test]
+    fn test_paginate_disabled() {
+        for per_page in [None, Some(0)] {
+            let pages = paginate("/tags/rust", 7, per_page);
+            assert_eq!(pages.len(), 1);
+            assert_eq!(pages[0].range, 0..7);
+            assert_eq!(pages[0].path, "/tags/rust/index.html");
+        }
+    }
+
+    #[test]
+    fn test_paginate_empty() {
+        let pages = paginate("/blog", 0, Some(10));
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].range, 0..0);
+    }
+}
@@ -0,0 +1,109 @@
+//! Minimal scoped thread pool for rendering pages concurrently.
+//!
+//! Work is distributed dynamically across a fixed number of scoped threads.
+//! Results always come back in input order, so output and logs stay
+//! deterministic regardless of scheduling.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Default worker count: one per available CPU.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+/// Shared progress across workers.
+struct Progress<R, F> {
+    results: Vec<Option<R>>,
+    /// Index of the first result not yet passed to `on_done`
+    flushed: usize,
+    on_done: F,
+}
+
+/// Apply `f` to every item using up to `jobs` threads.
+///
+/// `on_done` observes each result in input order as soon as that result and
+/// all earlier ones are available, which keeps progress logs ordered while
+/// work completes out of order. Returns all results in input order.
+pub fn map_ordered<T, R, F, D>(items: &[T], jobs: usize, f: F, on_done: D) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    D: FnMut(&R) + Send,
+{
+    let next = AtomicUsize::new(0);
+    let progress = Mutex::new(Progress {
+        results: items.iter().map(|_| None).collect(),
+        flushed: 0,
+        on_done,
+    });
+    let workers = jobs.clamp(1, items.len().max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = f(item);
+
+                let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                progress.results[i] = Some(result);
+                let Progress {
+                    results,
+                    flushed,
+                    on_done,
+                } = &mut *progress;
+                while let Some(Some(ready)) = results.get(*flushed) {
+                    on_done(ready);
+                    *flushed += 1;
+                }
+            });
+        }
+    });
+
+    progress
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .results
+        .into_iter()
+        .map(|r| r.expect("every item is processed before workers exit"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_ordered_preserves_order() {
+        let items: Vec<u64> = (0..100).collect();
+        let mut seen = Vec::new();
+
+        let results = map_ordered(&items, 8, |n| n * 2, |r| seen.push(*r));
+
+        let expected: Vec<u64> = (0..100).map(|n| n * 2).collect();
+        assert_eq!(results, expected);
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_map_ordered_single_job() {
+        let items = vec!["a", "b", "c"];
+        let results = map_ordered(&items, 1, |s| s.to_uppercase(), |_| {});
+        assert_eq!(results, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_map_ordered_empty() {
+        let items: Vec<u32> = Vec::new();
+        let results = map_ordered(&items, 4, |n| *n, |_| {});
+        assert!(results.is_empty());
+    }
+}
@@ -1,31 +1,101 @@
 //! Markdown to HTML rendering via pulldown-cmark with syntax highlighting.
 
+use crate::cache::FragmentCache;
+use crate::config::NavConfig;
+use crate::content::Content;
+use crate::error::Result;
 use crate::escape::{code_escape, html_escape};
 use crate::highlight::{highlight_code, Language};
-use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use crate::links::{LinkResolver, PageLinks};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 /// A heading anchor extracted from markdown content.
 #[derive(Debug, Clone, Serialize)]
 pub struct Anchor {
-    /// Heading ID attribute (slug)
+    /// Heading ID attribute (custom `{#id}` or slug, unique on the page)
     pub id: String,
     /// Heading text content
     pub label: String,
-    /// Heading level (2-6, h1 excluded)
+    /// Heading level (1-6)
     pub level: u8,
 }
 
-/// Render markdown content to HTML with syntax highlighting.
-/// Returns the HTML output and a list of extracted heading anchors.
-pub fn markdown_to_html(markdown: &str) -> (String, Vec<Anchor>) {
-    let options = Options::ENABLE_TABLES
+/// A table of contents entry, with the headings nested under it.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TocEntry {
+    /// Heading ID attribute
+    pub id: String,
+    /// Heading text content
+    pub label: String,
+    /// Heading level (1-6)
+    pub level: u8,
+    /// Deeper headings up to the next heading at this level or above
+    pub children: Vec<TocEntry>,
+}
+
+/// Heading levels listed in the table of contents (default: h2-h6).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TocLevels {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl Default for TocLevels {
+    fn default() -> Self {
+        Self { min: 2, max: 6 }
+    }
+}
+
+impl TocLevels {
+    fn contains(self, level: u8) -> bool {
+        (self.min..=self.max).contains(&level)
+    }
+}
+
+/// Paragraph replaced by the rendered table of contents.
+const TOC_MARKER: &str = "[[toc]]";
+
+/// Stand-in for the table of contents until every heading is known.
+/// CommonMark replaces NUL in the source, so content cannot produce it.
+const TOC_PLACEHOLDER: &str = "\0toc\0";
+
+/// Markdown extensions enabled for all content.
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
         | Options::ENABLE_FOOTNOTES
         | Options::ENABLE_STRIKETHROUGH
         | Options::ENABLE_TASKLISTS
-        | Options::ENABLE_MATH;
+        | Options::ENABLE_MATH
+        | Options::ENABLE_HEADING_ATTRIBUTES
+}
+
+/// Render markdown content to HTML with syntax highlighting.
+/// Returns the HTML output and the anchors of headings within `toc` levels.
+///
+/// With `links`, internal links (`@/blog/post.md`, `../post.md#anchor`) are
+/// resolved to output URLs, failing on missing pages or anchors; without,
+/// every link is written as-is. A `[[toc]]` paragraph is replaced by a
+/// `<nav class="toc">` list of the anchors.
+pub fn markdown_to_html(
+    markdown: &str,
+    links: Option<&PageLinks>,
+    toc: TocLevels,
+) -> Result<(String, Vec<Anchor>)> {
+    render_markdown(markdown, links, Some(toc))
+}
 
-    let parser = Parser::new_ext(markdown, options);
+/// Render markdown to HTML, as a full page with `toc` levels, or as a
+/// listing summary without: summary headings get no ids or anchor links and
+/// `[[toc]]` markers are dropped, since many summaries share one page.
+fn render_markdown(
+    markdown: &str,
+    links: Option<&PageLinks>,
+    toc: Option<TocLevels>,
+) -> Result<(String, Vec<Anchor>)> {
+    let parser = Parser::new_ext(markdown, markdown_options()).into_offset_iter();
     let mut html_output = String::new();
     let mut anchors = Vec::new();
     let mut code_block_lang: Option<String> = None;
@@ -39,9 +109,25 @@ pub fn markdown_to_html(markdown: &str) -> (String, Vec<Anchor>) {
     // Heading accumulation state
     let mut heading_level: Option<HeadingLevel> = None;
     let mut heading_text = String::new();
+    let mut heading_id: Option<String> = None; // Custom `{#id}`
+    let mut heading_attrs = String::new(); // Rendered `{.class key=value}`
+    let mut heading_ids = HeadingIds::new(markdown);
+
+    // Skipping the events of a `[[toc]]` paragraph
+    let mut in_toc_marker = false;
 
-    for event in parser {
+    for (event, range) in parser {
+        if in_toc_marker {
+            in_toc_marker = event != Event::End(TagEnd::Paragraph);
+            continue;
+        }
         match event {
+            Event::Start(Tag::Paragraph) if markdown[range.clone()].trim() == TOC_MARKER => {
+                if toc.is_some() {
+                    html_output.push_str(TOC_PLACEHOLDER);
+                }
+                in_toc_marker = true;
+            }
             Event::Start(Tag::CodeBlock(kind)) => {
                 // Extract language from code fence
                 code_block_lang = match kind {
@@ -134,10 +220,41 @@ pub fn markdown_to_html(markdown: &str) -> (String, Vec<Anchor>) {
                 image_alt_content = Some(String::new());
                 image_attrs = Some((dest_url.to_string(), title.to_string()));
             }
-            Event::Start(Tag::Heading { level, .. }) => {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                // Resolve internal links to output URLs
+                let dest_url = match links {
+                    Some(links) => match links.resolve(&dest_url) {
+                        Ok(Some(url)) => url.into(),
+                        Ok(None) => dest_url,
+                        Err(message) => {
+                            return Err(links.broken(markdown, range.start, &dest_url, message));
+                        }
+                    },
+                    None => dest_url,
+                };
+                html_output.push_str(&start_tag_to_html(&Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }));
+            }
+            Event::Start(Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            }) if toc.is_some() => {
                 // Begin accumulating heading text
                 heading_level = Some(level);
                 heading_text.clear();
+                heading_id = id.map(|id| id.to_string());
+                heading_attrs = heading_attributes(&classes, &attrs);
                 let level_num = level as u8;
                 html_output.push_str(&format!("<h{}", level_num));
                 // ID will be added at End event after we have the text
@@ -165,25 +282,29 @@ pub fn markdown_to_html(markdown: &str) -> (String, Vec<Anchor>) {
                     }
                 }
             }
-            Event::End(TagEnd::Heading(level)) => {
-                // Generate slug ID from heading text
-                let id = slugify(&heading_text);
+            Event::End(TagEnd::Heading(level)) if toc.is_some() => {
+                // Use the custom ID or a slug of the heading text, unique on the page
+                let id = heading_ids.assign(heading_id.take().as_deref(), &heading_text);
                 let level_num = level as u8;
 
                 // We need to go back and insert the id attribute and close the tag
                 // The heading was opened as "<hN" - find it and complete with id and >
                 if let Some(pos) = html_output.rfind(&format!("<h{}", level_num)) {
                     let insert_pos = pos + format!("<h{}", level_num).len();
-                    html_output.insert_str(insert_pos, &format!(" id=\"{}\">", id));
+                    html_output.insert_str(
+                        insert_pos,
+                        &format!(" id=\"{}\"{}>", html_escape(&id), heading_attrs),
+                    );
                 }
                 // Add pilcrow anchor link for deep-linking (hover-reveal via CSS)
                 html_output.push_str(&format!(
                     "<a class=\"heading-anchor\" href=\"#{}\">¶</a></h{}>\n",
-                    id, level_num
+                    html_escape(&id),
+                    level_num
                 ));
 
-                // Extract anchor for h2-h6 (skip h1)
-                if level_num >= 2 {
+                // Extract anchor for the table of contents
+                if toc.is_some_and(|toc| toc.contains(level_num)) {
                     anchors.push(Anchor {
                         id,
                         label: heading_text.clone(),
@@ -248,11 +369,345 @@ pub fn markdown_to_html(markdown: &str) -> (String, Vec<Anchor>) {
         }
     }
 
-    (html_output, anchors)
+    if html_output.contains(TOC_PLACEHOLDER) {
+        html_output = html_output.replace(TOC_PLACEHOLDER, &toc_html(&toc_tree(&anchors)));
+    }
+
+    Ok((html_output, anchors))
+}
+
+/// Nest a flat list of anchors by level.
+///
+/// Each heading holds the deeper headings that follow it; a heading that
+/// skips levels (h2, then h4) nests directly under the previous one.
+pub fn toc_tree(anchors: &[Anchor]) -> Vec<TocEntry> {
+    fn insert(entries: &mut Vec<TocEntry>, anchor: &Anchor) {
+        match entries.last_mut() {
+            Some(last) if last.level < anchor.level => insert(&mut last.children, anchor),
+            _ => entries.push(TocEntry {
+                id: anchor.id.clone(),
+                label: anchor.label.clone(),
+                level: anchor.level,
+                children: Vec::new(),
+            }),
+        }
+    }
+
+    let mut tree = Vec::new();
+    for anchor in anchors {
+        insert(&mut tree, anchor);
+    }
+    tree
+}
+
+/// Render a table of contents as nested lists, or nothing when empty.
+fn toc_html(entries: &[TocEntry]) -> String {
+    fn list(entries: &[TocEntry], out: &mut String) {
+        out.push_str("<ul>\n");
+        for entry in entries {
+            out.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a>",
+                html_escape(&entry.id),
+                html_escape(&entry.label)
+            ));
+            if !entry.children.is_empty() {
+                out.push('\n');
+                list(&entry.children, out);
+            }
+            out.push_str("</li>\n");
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if entries.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("<nav class=\"toc\">\n");
+    list(entries, &mut out);
+    out.push_str("</nav>\n");
+    out
+}
+
+/// Destinations of every link, in document order.
+pub fn link_dests(markdown: &str) -> Vec<String> {
+    Parser::new_ext(markdown, markdown_options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link { dest_url, .. }) => Some(dest_url.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// URLs of every link and image, including `href`/`src` attributes in raw
+/// HTML, in document order with the byte offset where each appears.
+pub fn link_urls(markdown: &str) -> Vec<(String, usize)> {
+    let mut urls = Vec::new();
+    for (event, range) in Parser::new_ext(markdown, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. })
+            | Event::Start(Tag::Image { dest_url, .. }) => {
+                urls.push((dest_url.to_string(), range.start))
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                for reference in crate::html::scan(&html).references {
+                    urls.push((reference.value, range.start + reference.offset));
+                }
+            }
+            _ => {}
+        }
+    }
+    urls
+}
+
+/// Ids of every heading, as [`markdown_to_html`] assigns them.
+///
+/// A cheap pass for link checking that skips highlighting and math.
+pub fn heading_ids(markdown: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut assigned = HeadingIds::new(markdown);
+    let mut heading_text: Option<String> = None;
+    let mut heading_id = None;
+    let mut image_depth = 0usize;
+
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        match event {
+            Event::Start(Tag::Heading { id, .. }) => {
+                heading_text = Some(String::new());
+                heading_id = id;
+            }
+            Event::Start(Tag::Image { .. }) => image_depth += 1,
+            Event::End(TagEnd::Image) => image_depth -= 1,
+            // Image alt text is not part of the id
+            Event::Text(text) if image_depth == 0 => {
+                if let Some(heading) = heading_text.as_mut() {
+                    heading.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(text) = heading_text.take() {
+                    ids.push(assigned.assign(heading_id.take().as_deref(), &text));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ids
+}
+
+/// Assigns unique heading ids within a page.
+///
+/// Custom ids (`## Setup {#install}`) are kept as written; ids derived from
+/// the heading text get a numeric suffix (`example`, `example-1`, ...) when
+/// an earlier heading or any custom id already uses them.
+struct HeadingIds {
+    /// Ids assigned so far
+    used: HashSet<String>,
+    /// Custom ids declared anywhere on the page
+    custom: HashSet<String>,
+}
+
+impl HeadingIds {
+    fn new(markdown: &str) -> Self {
+        let custom = Parser::new_ext(markdown, markdown_options())
+            .filter_map(|event| match event {
+                Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            used: HashSet::new(),
+            custom,
+        }
+    }
+
+    /// Id for the next heading, from its custom id or its text.
+    fn assign(&mut self, custom: Option<&str>, text: &str) -> String {
+        let base = match custom {
+            Some(id) => id.to_string(),
+            None => slugify(text),
+        };
+        let taken =
+            |id: &str| self.used.contains(id) || (custom.is_none() && self.custom.contains(id));
+
+        let mut id = base.clone();
+        let mut n = 0;
+        while taken(&id) {
+            n += 1;
+            id = format!("{base}-{n}");
+        }
+        self.used.insert(id.clone());
+        id
+    }
+}
+
+/// Render the classes and `data-*` attributes from `{.class data-key=value}`
+/// after a heading, with a leading space.
+///
+/// Other attributes (`onclick`, `style`, ...) are dropped, so markdown cannot
+/// inject scripts or styles through heading attributes.
+fn heading_attributes(classes: &[CowStr], attrs: &[(CowStr, Option<CowStr>)]) -> String {
+    let mut out = String::new();
+    if !classes.is_empty() {
+        let classes: Vec<_> = classes.iter().map(|c| c.as_ref()).collect();
+        out.push_str(&format!(" class=\"{}\"", html_escape(&classes.join(" "))));
+    }
+    for (key, value) in attrs.iter().filter(|(key, _)| is_data_attribute(key)) {
+        match value {
+            Some(value) => {
+                out.push_str(&format!(" {}=\"{}\"", html_escape(key), html_escape(value)))
+            }
+            None => out.push_str(&format!(" {}", html_escape(key))),
+        }
+    }
+    out
+}
+
+/// Whether `name` is a `data-*` attribute with a valid name.
+fn is_data_attribute(name: &str) -> bool {
+    name.strip_prefix("data-").is_some_and(|rest| {
+        !rest.is_empty()
+            && rest
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+/// Render the summary of a markdown body to HTML.
+///
+/// The summary is everything before a `<!-- more -->` marker, or the first
+/// top-level paragraph when there is no marker. Returns None for bodies
+/// without either.
+///
+/// Internal links are resolved with `links`, failing on broken ones. Headings
+/// are rendered without ids or anchor links.
+pub fn summary_to_html(markdown: &str, links: Option<&PageLinks>) -> Result<Option<String>> {
+    summary_markdown(markdown)
+        .map(|summary| render_markdown(summary, links, None).map(|(html, _)| html))
+        .transpose()
+}
+
+/// Rendered summaries of every page, shared by listings and feeds, plus the
+/// full bodies of feed entries.
+#[derive(Debug, Default)]
+pub struct Summaries {
+    /// Summary with page-relative links, by source path
+    pages: HashMap<PathBuf, String>,
+    /// Summary with absolute links, by source path
+    feeds: HashMap<PathBuf, String>,
+    /// Full body with absolute links, by source path
+    contents: HashMap<PathBuf, String>,
+}
+
+impl Summaries {
+    /// Render the summary of each of `contents` once, in both link forms,
+    /// reusing summaries from `fragments` where the page is unchanged.
+    pub fn new<'a>(
+        contents: impl IntoIterator<Item = &'a Content>,
+        links: &LinkResolver,
+        fragments: &mut FragmentCache,
+    ) -> Result<Self> {
+        let mut summaries = Self::default();
+        for content in contents {
+            let path = &content.source_path;
+            let dependencies = links.dependencies(content);
+            if let Some(html) = fragments.get_or_render("summary", content, dependencies, || {
+                summary_to_html(&content.body, Some(&links.page(content)))
+            })? {
+                summaries.pages.insert(path.clone(), html);
+            }
+            if let Some(html) =
+                fragments.get_or_render("feed summary", content, dependencies, || {
+                    summary_to_html(&content.body, Some(&links.page_absolute(content)))
+                })?
+            {
+                summaries.feeds.insert(path.clone(), html);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Render the full body of each of `posts` once, for feeds with
+    /// `content = "full"`, reusing bodies from `fragments` where the post is
+    /// unchanged.
+    pub fn render_feed_contents<'a>(
+        &mut self,
+        posts: impl IntoIterator<Item = &'a Content>,
+        links: &LinkResolver,
+        nav: &NavConfig,
+        fragments: &mut FragmentCache,
+    ) -> Result<()> {
+        for post in posts {
+            if self.contents.contains_key(&post.source_path) {
+                continue;
+            }
+            let html =
+                fragments.get_or_render("feed content", post, links.dependencies(post), || {
+                    let levels = post.frontmatter.toc_levels(nav);
+                    markdown_to_html(&post.body, Some(&links.page_absolute(post)), levels)
+                        .map(|(html, _)| Some(html))
+                })?;
+            self.contents
+                .insert(post.source_path.clone(), html.unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    /// Summary of `content` for listing pages.
+    pub fn page(&self, content: &Content) -> Option<&str> {
+        self.pages.get(&content.source_path).map(String::as_str)
+    }
+
+    /// Summary of `content` for feeds, with absolute links.
+    pub fn feed(&self, content: &Content) -> Option<&str> {
+        self.feeds.get(&content.source_path).map(String::as_str)
+    }
+
+    /// Full body of `content` for feeds, with absolute links, if rendered.
+    pub fn feed_content(&self, content: &Content) -> Option<&str> {
+        self.contents.get(&content.source_path).map(String::as_str)
+    }
+}
+
+/// Markdown source of the summary (see [`summary_to_html`]).
+fn summary_markdown(markdown: &str) -> Option<&str> {
+    let mut depth = 0usize;
+    let mut first_paragraph = None;
+
+    for (event, range) in Parser::new_ext(markdown, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Html(html) | Event::InlineHtml(html) if is_more_marker(&html) => {
+                let summary = markdown[..range.start].trim();
+                return (!summary.is_empty()).then_some(summary);
+            }
+            Event::Start(tag) => {
+                if depth == 0
+                    && first_paragraph.is_none()
+                    && tag == Tag::Paragraph
+                    && markdown[range.clone()].trim() != TOC_MARKER
+                {
+                    first_paragraph = Some(range);
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+
+    first_paragraph.map(|range| markdown[range].trim())
+}
+
+/// Whether raw HTML is the `<!-- more -->` summary marker.
+fn is_more_marker(html: &str) -> bool {
+    html.trim()
+        .strip_prefix("<!--")
+        .and_then(|s| s.strip_suffix("-->"))
+        .is_some_and(|inner| inner.trim() == "more")
 }
 
 /// Convert heading text to a URL-friendly slug ID.
-fn slugify(text: &str) -> String {
+pub(crate) fn slugify(text: &str) -> String {
     text.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
@@ -343,7 +798,7 @@ mod tests {
     #[test]
     fn test_basic_markdown() {
         let md = "# Hello\n\nThis is a *test*.";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
         // Heading includes pilcrow anchor for deep-linking
         assert!(html.contains(
             "<h1 id=\"hello\">Hello<a class=\"heading-anchor\" href=\"#hello\">¶</a></h1>"
@@ -354,7 +809,7 @@ mod tests {
     #[test]
     fn test_code_block_highlighting() {
         let md = "```rust\nfn main() {}\n```";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         // Should contain highlighted code
         assert!(html.contains("<pre><code"));
@@ -365,7 +820,7 @@ mod tests {
     #[test]
     fn test_code_block_unknown_language() {
         let md = "```unknown\nsome code\n```";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         // Should contain escaped code without highlighting spans
         assert!(html.contains("<pre><code"));
@@ -377,7 +832,7 @@ mod tests {
     #[test]
     fn test_inline_code() {
         let md = "Use `cargo run` to start.";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         assert!(html.contains("<code>cargo run</code>"));
     }
@@ -385,7 +840,7 @@ mod tests {
     #[test]
     fn test_image_alt_text() {
         let md = "![Beautiful sunset](sunset.jpg \"Evening sky\")";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         assert!(html.contains("alt=\"Beautiful sunset\""));
         assert!(html.contains("title=\"Evening sky\""));
@@ -395,7 +850,7 @@ mod tests {
     #[test]
     fn test_image_alt_text_no_title() {
         let md = "![Logo image](logo.png)";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         assert!(html.contains("alt=\"Logo image\""));
         assert!(html.contains("src=\"logo.png\""));
@@ -413,7 +868,7 @@ Install steps.
 Config details.
 #### Deep Heading
 "#;
-        let (html, anchors) = markdown_to_html(md);
+        let (html, anchors) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         // h1 should NOT be extracted (page title, not TOC)
         assert!(anchors.iter().all(|a| a.level >= 2));
@@ -443,6 +898,98 @@ Config details.
         assert!(html.contains("id=\"installation\""));
     }
 
+    #[test]
+    fn test_heading_ids_match_rendered_ids() {
+        let md = "# Title\n\n## Hello, World!\n\n### ![logo](a.png) Setup\n\nText [link](x.md).";
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        let ids = heading_ids(md);
+        assert_eq!(ids, vec!["title", "hello-world", "setup"]);
+        for id in &ids {
+            assert!(html.contains(&format!("id=\"{id}\"")));
+        }
+        assert_eq!(link_dests(md), vec!["x.md"]);
+    }
+
+    #[test]
+    fn test_duplicate_heading_ids() {
+        let md = "## Example\n\n## Example\n\n## Example\n\n### Example 1\n";
+        let (html, anchors) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        let ids: Vec<_> = anchors.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["example", "example-1", "example-2", "example-1-1"]
+        );
+        assert!(html.contains("<h2 id=\"example-2\">"));
+        assert!(html.contains("href=\"#example-2\""));
+        assert_eq!(heading_ids(md), ids);
+    }
+
+    #[test]
+    fn test_custom_heading_ids() {
+        let md = "## Setup\n\n## Installing {#setup .wide data-x=1}\n\n## Usage {#usage}\n";
+        let (html, anchors) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        assert!(html.contains(r#"<h2 id="setup" class="wide" data-x="1">Installing"#));
+        assert!(html.contains("href=\"#setup\""));
+
+        // The custom id wins; the derived one moves aside
+        let ids: Vec<_> = anchors.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["setup-1", "setup", "usage"]);
+        assert_eq!(anchors[1].label, "Installing");
+        assert_eq!(heading_ids(md), ids);
+    }
+
+    #[test]
+    fn test_heading_attributes_allow_only_data() {
+        let md = "## Title {.note data-level=2 onclick=alert(1) style=color:red data-=x}\n";
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        assert!(html.contains(r#"<h2 id="title" class="note" data-level="2">Title"#));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("style"));
+    }
+
+    #[test]
+    fn test_toc_tree() {
+        let md = "# Title\n\n## Install\n\n### Linux\n\n#### Nix\n\n### macOS\n\n## Usage\n\n#### Flags\n";
+        let (_, anchors) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        let tree = toc_tree(&anchors);
+
+        let top: Vec<_> = tree.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(top, vec!["install", "usage"]);
+        let install: Vec<_> = tree[0].children.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(install, vec!["linux", "macos"]);
+        assert_eq!(tree[0].children[0].children[0].id, "nix");
+        // Skipped levels nest directly under the previous heading
+        assert_eq!(tree[1].children[0].id, "flags");
+    }
+
+    #[test]
+    fn test_toc_levels() {
+        let md = "# Title\n\n## Install\n\n### Linux\n\n#### Nix\n";
+        let levels = TocLevels { min: 1, max: 3 };
+        let (_, anchors) = markdown_to_html(md, None, levels).unwrap();
+        let ids: Vec<_> = anchors.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["title", "install", "linux"]);
+    }
+
+    #[test]
+    fn test_toc_marker() {
+        let md = "Intro.\n\n[[toc]]\n\n## A & B\n\n### Sub\n\n## C\n";
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
+        assert!(html.contains(
+            "<p>Intro.</p>\n<nav class=\"toc\">\n<ul>\n\
+             <li><a href=\"#a-b\">A &amp; B</a>\n<ul>\n<li><a href=\"#sub\">Sub</a></li>\n</ul>\n</li>\n\
+             <li><a href=\"#c\">C</a></li>\n</ul>\n</nav>\n<h2"
+        ));
+        assert!(!html.contains("[[toc]]"));
+
+        // Inline mentions are left alone; a page without headings drops the marker
+        let (html, _) =
+            markdown_to_html("Use `[[toc]]` or [[toc]] here.", None, TocLevels::default()).unwrap();
+        assert!(html.contains("[[toc]] here"));
+        let (html, _) = markdown_to_html("[[toc]]\n\nText.", None, TocLevels::default()).unwrap();
+        assert_eq!(html, "<p>Text.</p>\n");
+    }
+
     #[test]
     fn test_slugify_edge_cases() {
         // Basic case
@@ -471,20 +1018,20 @@ Config details.
     fn test_link_url_escaping() {
         // Quote-breaking attack
         let md = r#"[click]("><script>alert(1)</script>)"#;
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
         assert!(!html.contains("<script>"), "script tags should be escaped");
         assert!(html.contains("&gt;"), "angle brackets should be escaped");
 
         // JavaScript URL (should be escaped, not executed)
         let md = r#"[click](javascript:alert(1))"#;
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
         assert!(html.contains("href=\"javascript:alert(1)\""));
     }
 
     #[test]
     fn test_link_title_escaping() {
         let md = r#"[text](url "title with \"quotes\"")"#;
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
         assert!(html.contains("&quot;"), "quotes in title should be escaped");
     }
 
@@ -492,7 +1039,7 @@ Config details.
     fn test_image_src_escaping() {
         // Quote-breaking attack in image src
         let md = r#"![alt]("><script>alert(1)</script>)"#;
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
         assert!(!html.contains("<script>"), "script tags should be escaped");
         assert!(
             html.contains("&quot;") || html.contains("&gt;"),
@@ -504,7 +1051,7 @@ Config details.
     fn test_unlabeled_code_block_preserves_quotes() {
         // Code block without language specifier should preserve quotes
         let md = "```\nContent-Security-Policy: default-src 'self';\n```";
-        let (html, _) = markdown_to_html(md);
+        let (html, _) = markdown_to_html(md, None, TocLevels::default()).unwrap();
 
         // Should be inside <pre><code>
         assert!(html.contains("<pre><code>"), "should have code block");
@@ -519,4 +1066,44 @@ Config details.
             "quotes should not be HTML-escaped in code blocks"
         );
     }
+
+    #[test]
+    fn test_summary_more_marker() {
+        let md = "# Title\n\nIntro with **bold**.\n\nSecond paragraph.\n\n<!-- more -->\n\nRest.";
+        let summary = summary_to_html(md, None).unwrap().unwrap();
+        assert!(summary.contains("Intro with <strong>bold</strong>."));
+        assert!(summary.contains("Second paragraph."));
+        assert!(!summary.contains("Rest."));
+
+        // Marker without spaces
+        let summary = summary_to_html("One.\n\nTwo.\n<!--more-->\nThree.", None)
+            .unwrap()
+            .unwrap();
+        assert!(summary.contains("Two."));
+        assert!(!summary.contains("Three."));
+    }
+
+    #[test]
+    fn test_summary_first_paragraph_fallback() {
+        let md = "## Heading\n\n> Quoted.\n\nFirst *real* paragraph.\n\nSecond.";
+        let summary = summary_to_html(md, None).unwrap().unwrap();
+        assert_eq!(summary, "<p>First <em>real</em> paragraph.</p>\n");
+    }
+
+    #[test]
+    fn test_summary_ignores_marker_in_code() {
+        let md = "Lead.\n\n```\n<!-- more -->\n```\n\nAfter.";
+        assert_eq!(
+            summary_to_html(md, None).unwrap().unwrap(),
+            "<p>Lead.</p>\n"
+        );
+        assert_eq!(summary_to_html("## Only a heading", None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_summary_headings_without_ids() {
+        let md = "## Setup\n\n[[toc]]\n\nSteps.\n\n<!-- more -->\n\nRest.";
+        let summary = summary_to_html(md, None).unwrap().unwrap();
+        assert_eq!(summary, "<h2>Setup</h2>\n<p>Steps.</p>\n");
+    }
 }
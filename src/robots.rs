@@ -0,0 +1,156 @@
+//! robots.txt generation from the `[robots]` config.
+
+use crate::config::{RobotsConfig, RobotsRule, SiteConfig};
+use crate::content::SiteManifest;
+use std::path::Path;
+
+/// Generate robots.txt for the site.
+///
+/// Every user-agent group gets its configured `Allow`/`Disallow` lines plus a
+/// `Disallow` line for each page marked `noindex`, since crawlers only obey
+/// the most specific group that matches them. Paths include the base path.
+/// The file ends with a `Sitemap:` line pointing at `{base_url}/sitemap.xml`.
+pub fn generate_robots(
+    manifest: &SiteManifest,
+    robots: &RobotsConfig,
+    config: &SiteConfig,
+    content_root: &Path,
+) -> String {
+    let base_path = config.base_path();
+    let noindex: Vec<String> = manifest
+        .content()
+        .filter(|c| c.frontmatter.noindex)
+        .map(|c| {
+            format!(
+                "{}{}",
+                base_path,
+                c.url_path(content_root, config.urls.style)
+            )
+        })
+        .collect();
+
+    let default_rule = RobotsRule {
+        user_agent: "*".to_string(),
+        allow: Vec::new(),
+        disallow: Vec::new(),
+    };
+    let rules = if robots.rules.is_empty() {
+        std::slice::from_ref(&default_rule)
+    } else {
+        robots.rules.as_slice()
+    };
+
+    build_robots_txt(
+        rules,
+        &noindex,
+        &format!("{}/sitemap.xml", config.site_url()),
+    )
+}
+
+/// Build the robots.txt string from user-agent groups.
+fn build_robots_txt(rules: &[RobotsRule], noindex: &[String], sitemap_url: &str) -> String {
+    let mut out = String::new();
+
+    for rule in rules {
+        out.push_str(&format!("User-agent: {}\n", rule.user_agent));
+        for path in &rule.allow {
+            out.push_str(&format!("Allow: {}\n", path));
+        }
+        for path in rule.disallow.iter().chain(noindex) {
+            out.push_str(&format!("Disallow: {}\n", path));
+        }
+        // A group needs at least one rule; an empty Disallow allows everything
+        if rule.allow.is_empty() && rule.disallow.is_empty() && noindex.is_empty() {
+            out.push_str("Disallow:\n");
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("Sitemap: {}\n", sitemap_url));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UrlStyle;
+
+    fn rule(user_agent: &str, allow: &[&str], disallow: &[&str]) -> RobotsRule {
+        RobotsRule {
+            user_agent: user_agent.to_string(),
+            allow: allow.iter().map(|p| p.to_string()).collect(),
+            disallow: disallow.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_build_robots_txt_empty_group() {
+        let txt = build_robots_txt(
+            &[rule("*", &[], &[])],
+            &[],
+            "https://example.com/sitemap.xml",
+        );
+        assert_eq!(
+            txt,
+            "User-agent: *\nDisallow:\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+    }
+
+    #[test]
+    fn test_build_robots_txt_adds_noindex_to_every_group() {
+        let rules = [
+            rule("*", &["/"], &["/drafts/"]),
+            rule("GPTBot", &[], &["/"]),
+        ];
+        let noindex = ["/private.html".to_string()];
+
+        let txt = build_robots_txt(&rules, &noindex, "https://example.com/sitemap.xml");
+
+        assert_eq!(
+            txt,
+            "User-agent: *\n\
+             Allow: /\n\
+             Disallow: /drafts/\n\
+             Disallow: /private.html\n\
+             \n\
+             User-agent: GPTBot\n\
+             Disallow: /\n\
+             Disallow: /private.html\n\
+             \n\
+             Sitemap: https://example.com/sitemap.xml\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_robots_from_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let content_dir = dir.path();
+        std::fs::write(content_dir.join("_index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(
+            content_dir.join("secret.md"),
+            "---\ntitle: Secret\nnoindex: true\n---\n",
+        )
+        .unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat).unwrap();
+        let config: SiteConfig = toml::from_str(
+            r#"
+            title = "Site"
+            author = "Author"
+            base_url = "https://example.com/"
+
+            [robots]
+            "#,
+        )
+        .unwrap();
+
+        let txt = generate_robots(
+            &manifest,
+            config.robots.as_ref().unwrap(),
+            &config,
+            content_dir,
+        );
+        assert!(txt.starts_with("User-agent: *\nDisallow: /secret.html\n"));
+        assert!(txt.ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+    }
+}
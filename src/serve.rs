@@ -0,0 +1,395 @@
+//! Development server with rebuild-on-change.
+//!
+//! Serves the output directory over plain HTTP on localhost and polls the
+//! content, static, template and config paths for changes. Build errors are
+//! reported in the terminal and served as an error page; the server keeps
+//! running until the next successful build.
+
+use crate::config::SiteConfig;
+use crate::error::{Error, Result};
+use crate::escape::{html_escape, percent_decode};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Default port for the development server.
+pub const DEFAULT_PORT: u16 = 8000;
+
+/// How often watched paths are polled for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Report of the most recent failed build, if any.
+type BuildState = Arc<Mutex<Option<String>>>;
+
+/// Build the site, serve the output directory and rebuild on change.
+///
+/// `build` is invoked once at startup and again whenever a watched file is
+/// added, removed or modified. Only returns if the server cannot start.
+pub fn serve(config_path: &Path, port: u16, build: impl Fn() -> Result<()>) -> Result<()> {
+    let config = SiteConfig::load(config_path)?;
+
+    // Resolve paths relative to config file location
+    let base_dir = config_path.parent().unwrap_or(Path::new("."));
+    let output_dir = base_dir.join(&config.paths.output);
+    let watched = vec![
+        base_dir.join(&config.paths.content),
+        base_dir.join(&config.paths.static_dir),
+        base_dir.join(&config.paths.templates),
+        config_path.to_path_buf(),
+    ];
+
+    let state: BuildState = Arc::new(Mutex::new(None));
+    rebuild(&build, &state);
+
+    let addr = format!("127.0.0.1:{port}");
+    let listener = TcpListener::bind(&addr).map_err(|e| Error::Serve {
+        addr: addr.clone(),
+        source: e,
+    })?;
+    // Serve under the base path, as the site will be deployed
+    let base_path = config.base_path();
+    eprintln!(
+        "serving {} at http://{}{}/",
+        output_dir.display(),
+        addr,
+        base_path
+    );
+
+    let server_state = Arc::clone(&state);
+    thread::spawn(move || {
+        for stream in listener.incoming().filter_map(|s| s.ok()) {
+            let root = output_dir.clone();
+            let base_path = base_path.clone();
+            let state = Arc::clone(&server_state);
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &root, &base_path, &state) {
+                    eprintln!("serve: {e}");
+                }
+            });
+        }
+    });
+
+    // Watch loop: rebuild whenever the snapshot of watched files changes
+    let mut last = snapshot(&watched);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(&watched);
+        if current != last {
+            last = current;
+            eprintln!("change detected, rebuilding...");
+            rebuild(&build, &state);
+        }
+    }
+}
+
+/// Run a build and record its outcome in the shared state.
+fn rebuild(build: &impl Fn() -> Result<()>, state: &BuildState) {
+    let start = Instant::now();
+    let outcome = build();
+    let mut last_error = state.lock().unwrap_or_else(|e| e.into_inner());
+
+    match outcome {
+        Ok(()) => {
+            eprintln!("build succeeded in {} ms", start.elapsed().as_millis());
+            *last_error = None;
+        }
+        Err(e) => {
+            let report = e.report();
+            eprintln!("{report}");
+            eprintln!("waiting for changes...");
+            *last_error = Some(report);
+        }
+    }
+}
+
+/// Collect modification times of every file under the watched paths.
+///
+/// Two snapshots differ when a file is added, removed or modified.
+fn snapshot(paths: &[PathBuf]) -> Vec<(PathBuf, SystemTime)> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        for entry in walkdir::WalkDir::new(path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let modified = entry
+                .metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((entry.into_path(), modified));
+        }
+    }
+
+    files
+}
+
+/// Answer a single HTTP request from the output directory.
+fn handle_connection(
+    mut stream: TcpStream,
+    root: &Path,
+    base_path: &str,
+    state: &BuildState,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Drain headers; the server does not use any of them
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("/");
+    let head_only = method == "HEAD";
+
+    if method != "GET" && !head_only {
+        return respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"",
+            false,
+        );
+    }
+
+    // A failed build takes precedence over stale output
+    let last_error = state.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(report) = last_error {
+        let page = error_page(&report);
+        return respond(
+            &mut stream,
+            "500 Internal Server Error",
+            "text/html; charset=utf-8",
+            page.as_bytes(),
+            head_only,
+        );
+    }
+
+    let file = match strip_base_path(target, base_path).and_then(|path| resolve_path(root, path)) {
+        Some(Resolved::File(path)) => Some(path),
+        Some(Resolved::Redirect(location)) => {
+            return redirect(&mut stream, &format!("{base_path}{location}"));
+        }
+        None => None,
+    };
+    match file.and_then(|path| fs::read(&path).ok().map(|b| (path, b))) {
+        Some((path, body)) => respond(&mut stream, "200 OK", content_type(&path), &body, head_only),
+        None => match fs::read(root.join("404.html")) {
+            // Serve the site's own 404 page, as static hosts do
+            Ok(body) => respond(
+                &mut stream,
+                "404 Not Found",
+                "text/html; charset=utf-8",
+                &body,
+                head_only,
+            ),
+            Err(_) => respond(
+                &mut stream,
+                "404 Not Found",
+                "text/plain; charset=utf-8",
+                b"404 Not Found",
+                head_only,
+            ),
+        },
+    }
+}
+
+/// Write a complete HTTP/1.1 response and close the connection.
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+    head_only: bool,
+) -> std::io::Result<()> {
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(header.as_bytes())?;
+    if !head_only {
+        stream.write_all(body)?;
+    }
+    stream.flush()
+}
+
+/// Write a `301` redirect to `location` and close the connection.
+fn redirect(stream: &mut TcpStream, location: &str) -> std::io::Result<()> {
+    let header = format!(
+        "HTTP/1.1 301 Moved Permanently\r\nLocation: {}\r\nContent-Length: 0\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        location
+    );
+    stream.write_all(header.as_bytes())?;
+    stream.flush()
+}
+
+/// What a request target maps to.
+#[derive(Debug, PartialEq)]
+enum Resolved {
+    /// A file inside the root
+    File(PathBuf),
+    /// A directory requested without its trailing slash: the same target
+    /// with the slash added, so relative links on its index resolve
+    Redirect(String),
+}
+
+/// Map a request target to a file inside `root`.
+///
+/// Strips query strings and fragments, percent-decodes the path, rejects
+/// anything that would escape `root`, and serves `index.html` for directories
+/// (redirecting first when the trailing slash is missing).
+fn resolve_path(root: &Path, target: &str) -> Option<Resolved> {
+    let path = target.split(['?', '#']).next().unwrap_or("");
+    let decoded = percent_decode(path)?;
+
+    let mut resolved = root.to_path_buf();
+    for component in Path::new(&decoded).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    if resolved.is_dir() {
+        if !path.ends_with('/') {
+            return Some(Resolved::Redirect(format!(
+                "{}/{}",
+                path,
+                &target[path.len()..]
+            )));
+        }
+        resolved.push("index.html");
+    }
+
+    resolved.is_file().then_some(Resolved::File(resolved))
+}
+
+/// Strip the site's base path (e.g., "/docs") from a request target.
+///
+/// Returns None for targets outside the base path.
+fn strip_base_path<'a>(target: &'a str, base_path: &str) -> Option<&'a str> {
+    let rest = target.strip_prefix(base_path)?;
+    if rest.is_empty() || rest.starts_with(['/', '?', '#']) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Guess a Content-Type from the file extension.
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Render a build error report as a self-refreshing HTML page (no JS).
+fn error_page(report: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="2">
+<title>sukr: build failed</title>
+</head>
+<body>
+<h1>Build failed</h1>
+<pre>{}</pre>
+<p>This page reloads automatically once the build succeeds.</p>
+</body>
+</html>
+"#,
+        html_escape(report)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_path_serves_index_for_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("blog")).unwrap();
+        fs::write(dir.path().join("index.html"), "home").unwrap();
+        fs::write(dir.path().join("blog/index.html"), "blog").unwrap();
+
+        assert_eq!(
+            resolve_path(dir.path(), "/"),
+            Some(Resolved::File(dir.path().join("index.html")))
+        );
+        assert_eq!(
+            resolve_path(dir.path(), "/blog/?page=2"),
+            Some(Resolved::File(dir.path().join("blog/index.html")))
+        );
+        assert!(resolve_path(dir.path(), "/missing.html").is_none());
+
+        // Directories without a trailing slash redirect first
+        assert_eq!(
+            resolve_path(dir.path(), "/blog?page=2"),
+            Some(Resolved::Redirect("/blog/?page=2".to_string()))
+        );
+        assert_eq!(
+            resolve_path(dir.path(), ""),
+            Some(Resolved::Redirect("/".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_strip_base_path() {
+        assert_eq!(strip_base_path("/blog/", ""), Some("/blog/"));
+        assert_eq!(strip_base_path("/docs/blog/", "/docs"), Some("/blog/"));
+        assert_eq!(strip_base_path("/docs", "/docs"), Some(""));
+        assert_eq!(strip_base_path("/docs?x=1", "/docs"), Some("?x=1"));
+        assert_eq!(strip_base_path("/docsearch/", "/docs"), None);
+        assert_eq!(strip_base_path("/blog/", "/docs"), None);
+    }
+
+    #[test]
+    fn test_resolve_path_rejects_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "home").unwrap();
+
+        assert!(resolve_path(dir.path(), "/../index.html").is_none());
+        assert!(resolve_path(dir.path(), "/%2e%2e/index.html").is_none());
+    }
+
+    #[test]
+    fn test_error_page_escapes_report() {
+        let page = error_page("error: invalid frontmatter in <post>");
+        assert!(page.contains("&lt;post&gt;"));
+        assert!(!page.contains("<script"));
+    }
+}
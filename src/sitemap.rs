@@ -1,69 +1,170 @@
 //! XML sitemap generation for SEO.
 
 use crate::config::SiteConfig;
-use crate::content::SiteManifest;
+use crate::content::{ChangeFreq, Content, SiteManifest};
+use crate::date::DateTime;
 use crate::escape::xml_escape;
 use std::path::Path;
 
+/// Maximum URLs per sitemap file (sitemaps.org protocol limit).
+pub(crate) const MAX_URLS: usize = 50_000;
+
 /// A URL entry for the sitemap.
 pub(crate) struct SitemapEntry {
     /// Absolute URL (e.g., "https://example.com/blog/post.html")
     pub loc: String,
-    /// Optional last modification date in W3C format (YYYY-MM-DD)
+    /// Optional last modification date in W3C format (date or RFC 3339 datetime)
     pub lastmod: Option<String>,
+    /// Optional expected change frequency
+    pub changefreq: Option<ChangeFreq>,
+    /// Optional priority relative to other pages (0.0 to 1.0)
+    pub priority: Option<f64>,
 }
 
-/// Generate an XML sitemap from the site manifest.
+impl SitemapEntry {
+    /// Entry for a content page, carrying its frontmatter metadata.
+    fn for_content(loc: String, content: &Content) -> Self {
+        Self {
+            loc,
+            lastmod: lastmod(content),
+            changefreq: content.frontmatter.changefreq,
+            priority: content.frontmatter.priority,
+        }
+    }
+
+    /// Entry for a generated page without frontmatter.
+    fn generated(loc: String, lastmod: Option<String>) -> Self {
+        Self {
+            loc,
+            lastmod,
+            changefreq: None,
+            priority: None,
+        }
+    }
+}
+
+/// Generate the XML sitemap files from the site manifest.
 ///
 /// Includes:
 /// - Homepage
 /// - Section indices
 /// - Section items (posts, projects, etc.)
 /// - Standalone pages
+/// - Taxonomy indexes and term pages
+/// - Later pages of paginated section and term listings
+///
+/// Pages with `sitemap: false` or `noindex: true` are left out. Returns
+/// `(file name, XML)` pairs: a single `sitemap.xml`, or, above [`MAX_URLS`]
+/// URLs, a `sitemap.xml` index followed by `sitemap-1.xml`, `sitemap-2.xml`, ...
 pub fn generate_sitemap(
     manifest: &SiteManifest,
     config: &SiteConfig,
     content_root: &Path,
-) -> String {
-    let base_url = config.base_url.trim_end_matches('/');
+) -> Vec<(String, String)> {
+    let base_url = &config.site_url();
+    let style = config.urls.style;
     let mut entries = Vec::new();
 
-    // Homepage
-    entries.push(SitemapEntry {
-        loc: format!("{}/index.html", base_url),
-        lastmod: None,
-    });
-
-    // Sections and their items
-    for section in &manifest.sections {
-        // Section index
-        entries.push(SitemapEntry {
-            loc: format!("{}/{}/index.html", base_url, section.name),
-            lastmod: section.index.frontmatter.date.clone(),
-        });
-
-        // Section items
-        if let Ok(items) = section.collect_items() {
-            for item in items {
-                let relative_path = item.output_path(content_root);
-                entries.push(SitemapEntry {
-                    loc: format!("{}/{}", base_url, relative_path.display()),
-                    lastmod: item.frontmatter.date.clone(),
-                });
+    // Homepage, sections and their items, standalone pages
+    for content in manifest.content() {
+        if content.frontmatter.in_sitemap() {
+            entries.push(SitemapEntry::for_content(
+                format!("{}{}", base_url, content.url_path(content_root, style)),
+                content,
+            ));
+        }
+    }
+
+    // Later pages of section listings, unless the section index is excluded
+    // (lastmod = newest item on the page)
+    for (section, items) in manifest.sections.iter().zip(&manifest.section_items) {
+        if !section.index.frontmatter.in_sitemap() {
+            continue;
+        }
+        for pager in section.pages(items.len()).into_iter().skip(1) {
+            entries.push(SitemapEntry::generated(
+                format!("{}{}", base_url, style.link(&pager.path)),
+                newest_lastmod(&items[pager.range]),
+            ));
+        }
+    }
+
+    // Taxonomy indexes and term pages (lastmod = newest tagged item, or
+    // newest on the page for later pages)
+    for taxonomy in &manifest.taxonomies {
+        entries.push(SitemapEntry::generated(
+            format!("{}{}", base_url, style.link(&taxonomy.path())),
+            None,
+        ));
+        for term in &taxonomy.terms {
+            entries.push(SitemapEntry::generated(
+                format!("{}{}", base_url, style.link(&taxonomy.term_path(term))),
+                newest_lastmod(&term.items),
+            ));
+            for pager in taxonomy.term_pages(term).into_iter().skip(1) {
+                entries.push(SitemapEntry::generated(
+                    format!("{}{}", base_url, style.link(&pager.path)),
+                    newest_lastmod(&term.items[pager.range]),
+                ));
             }
         }
     }
 
-    // Standalone pages
-    for page in &manifest.pages {
-        let relative_path = page.output_path(content_root);
-        entries.push(SitemapEntry {
-            loc: format!("{}/{}", base_url, relative_path.display()),
-            lastmod: page.frontmatter.date.clone(),
-        });
+    build_sitemaps(&entries, base_url, MAX_URLS)
+}
+
+/// Last modification date of a content item: `updated`, else `date`.
+///
+/// Plain dates are kept as `YYYY-MM-DD`; datetimes (including the
+/// space-separated `2024-01-15 09:30` form) are normalized to RFC 3339.
+fn lastmod(content: &Content) -> Option<String> {
+    let fm = &content.frontmatter;
+    let value = fm.updated.as_deref().or(fm.date.as_deref())?.trim();
+    let parsed = DateTime::parse(value)?;
+    Some(if value.len() == 10 {
+        value.to_string()
+    } else {
+        parsed.to_rfc3339()
+    })
+}
+
+/// Last modification date of the most recently updated of `items`.
+fn newest_lastmod(items: &[Content]) -> Option<String> {
+    items
+        .iter()
+        .max_by_key(|c| c.frontmatter.updated_time())
+        .and_then(lastmod)
+}
+
+/// Split entries into sitemap files of at most `max_urls` URLs each.
+///
+/// Small sites get a single `sitemap.xml`; larger ones get a `sitemap.xml`
+/// index pointing at numbered sitemaps.
+fn build_sitemaps(
+    entries: &[SitemapEntry],
+    base_url: &str,
+    max_urls: usize,
+) -> Vec<(String, String)> {
+    if entries.len() <= max_urls {
+        return vec![("sitemap.xml".to_string(), build_sitemap_xml(entries))];
     }
 
-    build_sitemap_xml(&entries)
+    let chunks: Vec<_> = entries.chunks(max_urls).collect();
+    let names: Vec<_> = (1..=chunks.len())
+        .map(|n| format!("sitemap-{}.xml", n))
+        .collect();
+
+    let mut files = vec![(
+        "sitemap.xml".to_string(),
+        build_sitemap_index_xml(base_url, &names),
+    )];
+    files.extend(
+        names
+            .into_iter()
+            .zip(chunks)
+            .map(|(name, chunk)| (name, build_sitemap_xml(chunk))),
+    );
+    files
 }
 
 /// Build the XML sitemap string from entries.
@@ -76,6 +177,15 @@ fn build_sitemap_xml(entries: &[SitemapEntry]) -> String {
         if let Some(ref date) = entry.lastmod {
             urls.push_str(&format!("    <lastmod>{}</lastmod>\n", xml_escape(date)));
         }
+        if let Some(changefreq) = entry.changefreq {
+            urls.push_str(&format!(
+                "    <changefreq>{}</changefreq>\n",
+                changefreq.as_str()
+            ));
+        }
+        if let Some(priority) = entry.priority {
+            urls.push_str(&format!("    <priority>{}</priority>\n", priority));
+        }
         urls.push_str("  </url>\n");
     }
 
@@ -89,9 +199,33 @@ fn build_sitemap_xml(entries: &[SitemapEntry]) -> String {
     )
 }
 
+/// Build a sitemap index pointing at the named sitemap files.
+fn build_sitemap_index_xml(base_url: &str, names: &[String]) -> String {
+    let mut sitemaps = String::new();
+
+    for name in names {
+        sitemaps.push_str("  <sitemap>\n");
+        sitemaps.push_str(&format!(
+            "    <loc>{}</loc>\n",
+            xml_escape(&format!("{}/{}", base_url, name))
+        ));
+        sitemaps.push_str("  </sitemap>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{}
+</sitemapindex>
+"#,
+        sitemaps.trim_end()
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::UrlStyle;
 
     #[test]
     fn test_xml_escape() {
@@ -106,6 +240,8 @@ mod tests {
         let entries = vec![SitemapEntry {
             loc: "https://example.com/index.html".to_string(),
             lastmod: None,
+            changefreq: None,
+            priority: None,
         }];
 
         let xml = build_sitemap_xml(&entries);
@@ -123,6 +259,8 @@ mod tests {
         let entries = vec![SitemapEntry {
             loc: "https://example.com/blog/post.html".to_string(),
             lastmod: Some("2026-01-31".to_string()),
+            changefreq: None,
+            priority: None,
         }];
 
         let xml = build_sitemap_xml(&entries);
@@ -137,14 +275,20 @@ mod tests {
             SitemapEntry {
                 loc: "https://example.com/index.html".to_string(),
                 lastmod: None,
+                changefreq: None,
+                priority: None,
             },
             SitemapEntry {
                 loc: "https://example.com/about.html".to_string(),
                 lastmod: Some("2026-01-15".to_string()),
+                changefreq: None,
+                priority: None,
             },
             SitemapEntry {
                 loc: "https://example.com/blog/index.html".to_string(),
                 lastmod: None,
+                changefreq: None,
+                priority: None,
             },
         ];
 
@@ -165,6 +309,8 @@ mod tests {
         let entries = vec![SitemapEntry {
             loc: "https://example.com/search?q=foo&bar=baz".to_string(),
             lastmod: None,
+            changefreq: None,
+            priority: None,
         }];
 
         let xml = build_sitemap_xml(&entries);
@@ -173,4 +319,155 @@ mod tests {
         assert!(xml.contains("&amp;"));
         assert!(!xml.contains("?q=foo&bar")); // Raw & should not appear
     }
+
+    #[test]
+    fn test_build_sitemap_xml_changefreq_and_priority() {
+        let entries = vec![SitemapEntry {
+            loc: "https://example.com/index.html".to_string(),
+            lastmod: None,
+            changefreq: Some(ChangeFreq::Weekly),
+            priority: Some(0.8),
+        }];
+
+        let xml = build_sitemap_xml(&entries);
+
+        assert!(xml.contains("<changefreq>weekly</changefreq>"));
+        assert!(xml.contains("<priority>0.8</priority>"));
+    }
+
+    #[test]
+    fn test_build_sitemaps_splits_into_index() {
+        let entries: Vec<_> = (0..5)
+            .map(|i| SitemapEntry::generated(format!("https://example.com/{i}.html"), None))
+            .collect();
+
+        let files = build_sitemaps(&entries, "https://example.com", 5);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "sitemap.xml");
+        assert!(files[0].1.contains("<urlset"));
+
+        let files = build_sitemaps(&entries, "https://example.com", 2);
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "sitemap.xml",
+                "sitemap-1.xml",
+                "sitemap-2.xml",
+                "sitemap-3.xml"
+            ]
+        );
+        let index = &files[0].1;
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<loc>https://example.com/sitemap-3.xml</loc>"));
+        assert_eq!(files[1].1.matches("<url>").count(), 2);
+        assert_eq!(files[3].1.matches("<url>").count(), 1);
+    }
+
+    #[test]
+    fn test_lastmod_normalizes_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = |name: &str, frontmatter: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("---\ntitle: T\n{frontmatter}\n---\n")).unwrap();
+            Content::from_path(&path, crate::content::ContentKind::Page).unwrap()
+        };
+
+        let dated = page("a.md", "date: 2024-01-15");
+        assert_eq!(lastmod(&dated).as_deref(), Some("2024-01-15"));
+        let spaced = page("b.md", "date: 2024-01-15 09:30");
+        assert_eq!(lastmod(&spaced).as_deref(), Some("2024-01-15T09:30:00Z"));
+        let updated = page(
+            "c.md",
+            "date: 2024-01-15\nupdated: 2024-02-01T08:00:00+02:00",
+        );
+        assert_eq!(
+            lastmod(&updated).as_deref(),
+            Some("2024-02-01T08:00:00+02:00")
+        );
+        assert_eq!(lastmod(&page("d.md", "")), None);
+    }
+
+    #[test]
+    fn test_generate_sitemap_excludes_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let content_dir = dir.path();
+        std::fs::write(content_dir.join("_index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(
+            content_dir.join("about.md"),
+            "---\ntitle: About\nchangefreq: monthly\npriority: 0.5\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            content_dir.join("hidden.md"),
+            "---\ntitle: Hidden\nsitemap: false\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            content_dir.join("private.md"),
+            "---\ntitle: Private\nnoindex: true\n---\n",
+        )
+        .unwrap();
+
+        let manifest = SiteManifest::discover(content_dir, false, &[], UrlStyle::Flat).unwrap();
+        let config: SiteConfig = toml::from_str(
+            r#"
+            title = "Site"
+            author = "Author"
+            base_url = "https://example.com/"
+            "#,
+        )
+        .unwrap();
+
+        let files = generate_sitemap(&manifest, &config, content_dir);
+        assert_eq!(files.len(), 1);
+        let xml = &files[0].1;
+        assert!(xml.contains("<loc>https://example.com/index.html</loc>"));
+        assert!(xml.contains("<loc>https://example.com/about.html</loc>"));
+        assert!(xml.contains("<changefreq>monthly</changefreq>"));
+        assert!(xml.contains("<priority>0.5</priority>"));
+        assert!(!xml.contains("hidden.html"));
+        assert!(!xml.contains("private.html"));
+    }
+
+    #[test]
+    fn test_generate_sitemap_includes_later_listing_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let content_dir = dir.path();
+        std::fs::create_dir(content_dir.join("blog")).unwrap();
+        std::fs::write(content_dir.join("_index.md"), "---\ntitle: Home\n---\n").unwrap();
+        std::fs::write(
+            content_dir.join("blog/_index.md"),
+            "---\ntitle: Blog\npaginate_by: 1\n---\n",
+        )
+        .unwrap();
+        for (slug, date) in [("old", "2024-01-01"), ("new", "2024-02-01")] {
+            std::fs::write(
+                content_dir.join(format!("blog/{slug}.md")),
+                format!("---\ntitle: {slug}\ndate: {date}\ntaxonomies:\n  tags: [rust]\n---\n"),
+            )
+            .unwrap();
+        }
+
+        let mut tags = crate::config::TaxonomyConfig::new("tags");
+        tags.paginate_by = Some(1);
+        let manifest = SiteManifest::discover(content_dir, false, &[tags], UrlStyle::Flat).unwrap();
+        let config: SiteConfig = toml::from_str(
+            r#"
+            title = "Site"
+            author = "Author"
+            base_url = "https://example.com/"
+            "#,
+        )
+        .unwrap();
+
+        let xml = &generate_sitemap(&manifest, &config, content_dir)[0].1;
+        assert!(xml.contains(
+            "<loc>https://example.com/blog/page/2/index.html</loc>\n    <lastmod>2024-01-01</lastmod>"
+        ));
+        assert!(xml.contains(
+            "<loc>https://example.com/tags/rust/page/2/index.html</loc>\n    <lastmod>2024-01-01</lastmod>"
+        ));
+        assert!(!xml.contains("page/1/"));
+    }
 }
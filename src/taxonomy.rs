@@ -0,0 +1,218 @@
+//! Taxonomy grouping (tags, categories, ...) for listing pages and per-term feeds.
+
+use crate::config::{FeedFormat, TaxonomyConfig};
+use crate::content::Content;
+use crate::pagination::{self, Pager};
+use crate::render::slugify;
+
+/// A taxonomy (e.g., "tags") with every term used across the site.
+#[derive(Debug)]
+pub struct Taxonomy {
+    /// Taxonomy name, also its output directory (e.g., "tags")
+    pub name: String,
+    /// Items per term listing page (None: no pagination)
+    pub paginate_by: Option<usize>,
+    /// Whether each term gets feeds
+    pub feed: bool,
+    /// Terms sorted by slug
+    pub terms: Vec<Term>,
+}
+
+/// A single taxonomy term and the content tagged with it.
+#[derive(Debug)]
+pub struct Term {
+    /// Display name (lexicographically smallest spelling, for determinism)
+    pub name: String,
+    /// URL-safe slug derived from the name
+    pub slug: String,
+    /// Tagged items, newest first then by title
+    pub items: Vec<Content>,
+}
+
+impl Taxonomy {
+    /// Group items by their frontmatter terms for the configured taxonomy.
+    ///
+    /// Terms whose names slugify identically ("Rust", "rust") are merged.
+    pub fn build<'a>(
+        config: &TaxonomyConfig,
+        items: impl IntoIterator<Item = &'a Content>,
+    ) -> Self {
+        let mut terms: Vec<Term> = Vec::new();
+
+        for item in items {
+            for term_name in item.frontmatter.terms(&config.name) {
+                let slug = slugify(term_name);
+                if slug.is_empty() {
+                    continue;
+                }
+                let term = match terms.iter().position(|t| t.slug == slug) {
+                    Some(i) => &mut terms[i],
+                    None => {
+                        terms.push(Term {
+                            name: term_name.clone(),
+                            slug,
+                            items: Vec::new(),
+                        });
+                        terms.last_mut().unwrap()
+                    }
+                };
+                if *term_name < term.name {
+                    term.name = term_name.clone();
+                }
+                if !term.items.iter().any(|c| c.source_path == item.source_path) {
+                    term.items.push(item.clone());
+                }
+            }
+        }
+
+        terms.sort_by(|a, b| a.slug.cmp(&b.slug));
+        for term in &mut terms {
+            term.items.sort_by(|a, b| {
+                b.frontmatter
+                    .date_time()
+                    .cmp(&a.frontmatter.date_time())
+                    .then_with(|| a.frontmatter.title.cmp(&b.frontmatter.title))
+            });
+        }
+
+        Self {
+            name: config.name.clone(),
+            paginate_by: config.paginate_by,
+            feed: config.feed,
+            terms,
+        }
+    }
+
+    /// URL path of the taxonomy index (e.g., "/tags/index.html").
+    pub fn path(&self) -> String {
+        format!("/{}/index.html", self.name)
+    }
+
+    /// URL path of a term listing (e.g., "/tags/rust/index.html").
+    pub fn term_path(&self, term: &Term) -> String {
+        pagination::page_path(&self.term_dir(term), 1)
+    }
+
+    /// Pages of a term listing, split by `paginate_by`.
+    pub fn term_pages(&self, term: &Term) -> Vec<Pager> {
+        pagination::paginate(&self.term_dir(term), term.items.len(), self.paginate_by)
+    }
+
+    /// URL directory of a term (e.g., "/tags/rust").
+    fn term_dir(&self, term: &Term) -> String {
+        format!("/{}/{}", self.name, term.slug)
+    }
+
+    /// URL path of a term's feed in `format` (e.g., "/tags/rust/feed.xml").
+    pub fn term_feed_path(&self, term: &Term, format: FeedFormat) -> String {
+        format!("{}/{}", self.term_dir(term), format.file_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentKind, Frontmatter};
+    use std::path::PathBuf;
+
+    fn item(slug: &str, date: &str, tags: &[&str]) -> Content {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        Content {
+            kind: ContentKind::Post,
+            frontmatter: Frontmatter {
+                title: slug.to_string(),
+                description: None,
+                date: Some(date.to_string()),
+                updated: None,
+                taxonomies: [("tags".to_string(), tags.clone())].into(),
+                tags,
+                weight: None,
+                link_to: None,
+                nav_label: None,
+                section_type: None,
+                template: None,
+                toc: None,
+                toc_min_level: None,
+                toc_max_level: None,
+                draft: false,
+                publish_date: None,
+                paginate_by: None,
+                sort_by: None,
+                sort_order: None,
+                feed: None,
+                section_feed: false,
+                sitemap: true,
+                noindex: false,
+                changefreq: None,
+                priority: None,
+                aliases: Vec::new(),
+                slug: None,
+                permalink: None,
+            },
+            body: String::new(),
+            source_path: PathBuf::from(format!("content/blog/{slug}.md")),
+            slug: slug.to_string(),
+            permalink: None,
+        }
+    }
+
+    #[test]
+    fn test_build_groups_and_sorts() {
+        let items = vec![
+            item("new", "2026-02-01", &["rust"]),
+            item("old", "2026-01-01", &["Rust", "web"]),
+            item("other", "2026-01-15", &["Nix"]),
+        ];
+
+        let tags = Taxonomy::build(&TaxonomyConfig::new("tags"), &items);
+
+        let slugs: Vec<_> = tags.terms.iter().map(|t| t.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["nix", "rust", "web"]);
+
+        // "Rust" and "rust" merge, keeping the smallest spelling
+        let rust = &tags.terms[1];
+        assert_eq!(rust.name, "Rust");
+        assert_eq!(rust.items.len(), 2);
+        assert_eq!(rust.items[0].slug, "new"); // Newest first
+    }
+
+    #[test]
+    fn test_build_deduplicates_repeated_terms() {
+        let items = vec![item("post", "2026-01-01", &["rust", "Rust"])];
+        let tags = Taxonomy::build(&TaxonomyConfig::new("tags"), &items);
+        assert_eq!(tags.terms.len(), 1);
+        assert_eq!(tags.terms[0].items.len(), 1);
+    }
+
+    #[test]
+    fn test_paths() {
+        let items = vec![item("post", "2026-01-01", &["Rust Lang"])];
+        let tags = Taxonomy::build(&TaxonomyConfig::new("tags"), &items);
+        let term = &tags.terms[0];
+        assert_eq!(tags.path(), "/tags/index.html");
+        assert_eq!(tags.term_path(term), "/tags/rust-lang/index.html");
+        assert_eq!(
+            tags.term_feed_path(term, FeedFormat::Atom),
+            "/tags/rust-lang/feed.xml"
+        );
+        assert_eq!(
+            tags.term_feed_path(term, FeedFormat::Json),
+            "/tags/rust-lang/feed.json"
+        );
+    }
+
+    #[test]
+    fn test_term_pages() {
+        let items: Vec<_> = (1..=5)
+            .map(|i| item(&format!("post{i}"), "2026-01-01", &["rust"]))
+            .collect();
+        let mut config = TaxonomyConfig::new("tags");
+        config.paginate_by = Some(2);
+
+        let tags = Taxonomy::build(&config, &items);
+        let pages = tags.term_pages(&tags.terms[0]);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].path, "/tags/rust/index.html");
+        assert_eq!(pages[2].path, "/tags/rust/page/3/index.html");
+    }
+}
@@ -1,19 +1,23 @@
 //! Tera-based template engine for runtime HTML generation.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use serde::Serialize;
 use tera::{Context, Tera, Value};
 
-use crate::config::SiteConfig;
-use crate::content::{Content, NavItem};
+use crate::config::{FeedFormat, SiteConfig, UrlStyle};
+use crate::content::{Content, NavItem, Section};
 use crate::error::{Error, Result};
-use crate::render::Anchor;
+use crate::pagination::Pager;
+use crate::render::{self, Anchor, Summaries};
+use crate::taxonomy::{Taxonomy, Term};
 
 /// Runtime template engine wrapping Tera.
 pub struct TemplateEngine {
     tera: Tera,
+    /// Links to the site-wide feeds, available to every template
+    feeds: Vec<FeedLinkContext>,
 }
 
 impl TemplateEngine {
@@ -25,7 +29,20 @@ impl TemplateEngine {
         // Register custom filters
         tera.register_filter("strip_parens", strip_parens_filter);
 
-        Ok(Self { tera })
+        Ok(Self {
+            tera,
+            feeds: Vec::new(),
+        })
+    }
+
+    /// Set the site-wide feed links exposed as `feeds` in every template.
+    pub fn set_feeds(&mut self, feeds: Vec<FeedLinkContext>) {
+        self.feeds = feeds;
+    }
+
+    /// Whether a template with the given name was loaded.
+    pub fn has_template(&self, template_name: &str) -> bool {
+        self.tera.get_template_names().any(|n| n == template_name)
     }
 
     /// Render a template by name with the given context.
@@ -56,6 +73,7 @@ impl TemplateEngine {
         );
         ctx.insert("content", html_body);
         ctx.insert("anchors", anchors);
+        ctx.insert("toc", &render::toc_tree(anchors));
         self.render("page.html", &ctx)
     }
 
@@ -82,40 +100,132 @@ impl TemplateEngine {
         );
         ctx.insert("content", html_body);
         ctx.insert("anchors", anchors);
+        ctx.insert("toc", &render::toc_tree(anchors));
+        self.render(template, &ctx)
+    }
+
+    /// Render the 404 page, from `content/404.md` when present.
+    ///
+    /// Uses the page's `template`, else `404.html`, else `page.html`. Hosts
+    /// serve this page at arbitrary depths, so `prefix` is the base path
+    /// and links resolve from the site root.
+    pub fn render_not_found(
+        &self,
+        content: Option<&Content>,
+        html_body: &str,
+        config: &SiteConfig,
+        nav: &[NavItem],
+        anchors: &[Anchor],
+    ) -> Result<String> {
+        let fm = content.map(|c| &c.frontmatter);
+        let template = match fm.and_then(|fm| fm.template.as_deref()) {
+            Some(template) => template,
+            None if self.has_template("404.html") => "404.html",
+            None => "page.html",
+        };
+
+        let mut ctx = self.base_context("/404.html", config, nav);
+        ctx.insert("prefix", &config.base_path());
+        ctx.insert("title", fm.map_or("Page Not Found", |fm| fm.title.as_str()));
+        if let Some(fm) = fm {
+            ctx.insert("page", &FrontmatterContext::new(fm, config));
+        }
+        ctx.insert("content", html_body);
+        ctx.insert("anchors", anchors);
+        ctx.insert("toc", &render::toc_tree(anchors));
         self.render(template, &ctx)
     }
 
-    /// Render a section index page (blog index, projects index).
+    /// Render one page of a section index (blog index, projects index).
+    ///
+    /// `items` holds only the items on this page, as described by `pager`.
     pub fn render_section(
         &self,
-        section: &Content,
-        section_type: &str,
+        section: &Section,
         items: &[ContentContext],
-        page_path: &str,
+        subsections: &[SectionContext],
+        pager: &Pager,
         config: &SiteConfig,
         nav: &[NavItem],
     ) -> Result<String> {
-        let template = format!("section/{}.html", section_type);
+        let template = format!("section/{}.html", section.section_type);
 
-        let mut ctx = self.base_context(page_path, config, nav);
-        ctx.insert("title", &section.frontmatter.title);
+        let mut ctx = self.base_context(&pager.path, config, nav);
+        ctx.insert("title", &section.index.frontmatter.title);
         ctx.insert(
             "section",
-            &FrontmatterContext::new(&section.frontmatter, config),
+            &FrontmatterContext::new(&section.index.frontmatter, config),
         );
         ctx.insert("items", items);
+        ctx.insert("subsections", subsections);
+        ctx.insert("section_feeds", &FeedLinkContext::section(section, config));
+        ctx.insert(
+            "paginator",
+            &PaginatorContext::new(pager, items, config.urls.style),
+        );
         self.render(&template, &ctx)
     }
 
+    /// Render a taxonomy index listing every term (e.g., /tags/index.html).
+    pub fn render_taxonomy_list(
+        &self,
+        taxonomy: &Taxonomy,
+        page_path: &str,
+        config: &SiteConfig,
+        nav: &[NavItem],
+    ) -> Result<String> {
+        let terms: Vec<_> = taxonomy
+            .terms
+            .iter()
+            .map(|term| TermContext::new(taxonomy, term, config))
+            .collect();
+
+        let mut ctx = self.base_context(page_path, config, nav);
+        ctx.insert("title", &taxonomy.name);
+        ctx.insert("taxonomy", &taxonomy.name);
+        ctx.insert("terms", &terms);
+        self.render("taxonomy/list.html", &ctx)
+    }
+
+    /// Render one page of a term listing (e.g., /tags/rust/index.html).
+    ///
+    /// `items` holds only the items on this page, as described by `pager`.
+    pub fn render_taxonomy_term(
+        &self,
+        taxonomy: &Taxonomy,
+        term: &Term,
+        items: &[ContentContext],
+        pager: &Pager,
+        config: &SiteConfig,
+        nav: &[NavItem],
+    ) -> Result<String> {
+        let mut ctx = self.base_context(&pager.path, config, nav);
+        ctx.insert("title", &term.name);
+        ctx.insert("taxonomy", &taxonomy.name);
+        ctx.insert("term", &TermContext::new(taxonomy, term, config));
+        ctx.insert("items", items);
+        ctx.insert(
+            "paginator",
+            &PaginatorContext::new(pager, items, config.urls.style),
+        );
+        self.render("taxonomy/single.html", &ctx)
+    }
+
     /// Build base context with common variables.
+    ///
+    /// `page_path` is the output path (e.g., "/blog/index.html"); templates
+    /// see it in link form, matching nav and listing paths.
     fn base_context(&self, page_path: &str, config: &SiteConfig, nav: &[NavItem]) -> Context {
+        let page_path = config.urls.style.link(page_path);
         let mut ctx = Context::new();
         ctx.insert("config", &ConfigContext::from(config));
         ctx.insert("nav", nav);
-        ctx.insert("page_path", page_path);
-        ctx.insert("prefix", &relative_prefix(page_path));
-        // Trimmed base_url for canonical links
-        ctx.insert("base_url", config.base_url.trim_end_matches('/'));
+        ctx.insert("feeds", &self.feeds);
+        ctx.insert("prefix", &relative_prefix(&page_path));
+        ctx.insert("page_path", &page_path);
+        // Site root URL (including any base path) for canonical links
+        ctx.insert("base_url", &config.site_url());
+        ctx.insert("base_path", &config.base_path());
         ctx
     }
 }
@@ -161,11 +271,18 @@ pub struct FrontmatterContext {
     pub title: String,
     pub description: Option<String>,
     pub date: Option<String>,
+    pub updated: Option<String>,
     pub tags: Vec<String>,
+    /// Terms per taxonomy name (includes "tags")
+    pub taxonomies: BTreeMap<String, Vec<String>>,
     pub weight: Option<i64>,
     pub link_to: Option<String>,
     /// Enable table of contents (anchor nav in sidebar)
     pub toc: bool,
+    /// Unfinished content (only rendered with --drafts)
+    pub draft: bool,
+    /// Ask search engines not to index the page
+    pub noindex: bool,
 }
 
 impl FrontmatterContext {
@@ -175,10 +292,14 @@ impl FrontmatterContext {
             title: fm.title.clone(),
             description: fm.description.clone(),
             date: fm.date.clone(),
+            updated: fm.updated.clone(),
             tags: fm.tags.clone(),
+            taxonomies: fm.taxonomies.clone(),
             weight: fm.weight,
             link_to: fm.link_to.clone(),
             toc: fm.toc.unwrap_or(config.nav.toc),
+            draft: fm.draft,
+            noindex: fm.noindex,
         }
     }
 }
@@ -188,17 +309,156 @@ impl FrontmatterContext {
 pub struct ContentContext {
     pub frontmatter: FrontmatterContext,
     pub body: String,
+    /// Rendered HTML summary (before `<!-- more -->`, or the first paragraph)
+    pub summary: Option<String>,
     pub slug: String,
     pub path: String,
 }
 
 impl ContentContext {
-    pub fn from_content(content: &Content, content_dir: &Path, config: &SiteConfig) -> Self {
+    pub fn from_content(
+        content: &Content,
+        content_dir: &Path,
+        config: &SiteConfig,
+        summaries: &Summaries,
+    ) -> Self {
         Self {
             frontmatter: FrontmatterContext::new(&content.frontmatter, config),
             body: content.body.clone(),
+            summary: summaries.page(content).map(str::to_string),
             slug: content.slug.clone(),
-            path: format!("/{}", content.output_path(content_dir).display()),
+            path: content.url_path(content_dir, config.urls.style),
+        }
+    }
+}
+
+/// Subsection context for section listings.
+#[derive(Serialize)]
+pub struct SectionContext {
+    pub frontmatter: FrontmatterContext,
+    /// Directory path relative to the content root (e.g., "docs/guides")
+    pub name: String,
+    /// URL path of the subsection index
+    pub path: String,
+    /// Links to the subsection's own feeds (empty without `section_feed`)
+    pub feeds: Vec<FeedLinkContext>,
+}
+
+impl SectionContext {
+    pub fn new(section: &Section, config: &SiteConfig) -> Self {
+        Self {
+            frontmatter: FrontmatterContext::new(&section.index.frontmatter, config),
+            name: section.name.clone(),
+            path: config
+                .urls
+                .style
+                .link(&format!("/{}/index.html", section.name)),
+            feeds: FeedLinkContext::section(section, config),
+        }
+    }
+}
+
+/// Taxonomy term context for taxonomy templates.
+#[derive(Serialize)]
+pub struct TermContext {
+    pub name: String,
+    pub slug: String,
+    /// Number of items with this term
+    pub count: usize,
+    /// URL path of the term listing page
+    pub path: String,
+    /// URL path of the term's feed in the first configured format
+    /// (None when the taxonomy has no feeds)
+    pub feed_path: Option<String>,
+    /// Links to the term's feeds in every configured format
+    pub feeds: Vec<FeedLinkContext>,
+}
+
+impl TermContext {
+    pub fn new(taxonomy: &Taxonomy, term: &Term, config: &SiteConfig) -> Self {
+        let feed_format = config.feed.formats.first().filter(|_| taxonomy.feed);
+        Self {
+            name: term.name.clone(),
+            slug: term.slug.clone(),
+            count: term.items.len(),
+            path: config.urls.style.link(&taxonomy.term_path(term)),
+            feed_path: feed_format.map(|&format| taxonomy.term_feed_path(term, format)),
+            feeds: if taxonomy.feed {
+                FeedLinkContext::all(config, |format| taxonomy.term_feed_path(term, format))
+            } else {
+                Vec::new()
+            },
+        }
+    }
+}
+
+/// Feed link context for `<link rel="alternate">` tags.
+#[derive(Debug, Serialize)]
+pub struct FeedLinkContext {
+    /// Feed format ("atom", "rss" or "json")
+    pub format: FeedFormat,
+    /// MIME type for the link's `type` attribute
+    pub mime_type: &'static str,
+    /// URL path of the feed
+    pub path: String,
+}
+
+impl FeedLinkContext {
+    /// One link per configured feed format, with paths from `path`.
+    pub fn all(config: &SiteConfig, path: impl Fn(FeedFormat) -> String) -> Vec<Self> {
+        config
+            .feed
+            .formats
+            .iter()
+            .map(|&format| Self {
+                format,
+                mime_type: format.mime_type(),
+                path: path(format),
+            })
+            .collect()
+    }
+
+    /// Links to a section's own feeds (empty without `section_feed`).
+    pub fn section(section: &Section, config: &SiteConfig) -> Vec<Self> {
+        if section.index.frontmatter.section_feed {
+            Self::all(config, |format| section.feed_path(format))
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Paginator context for paginated listings.
+#[derive(Serialize)]
+pub struct PaginatorContext<'a> {
+    /// 1-based number of the current page
+    pub current_page: usize,
+    pub total_pages: usize,
+    /// Number of items across all pages
+    pub total_items: usize,
+    /// URL path of the first page
+    pub first: String,
+    /// URL path of the last page
+    pub last: String,
+    /// URL path of the previous page, if any
+    pub prev: Option<String>,
+    /// URL path of the next page, if any
+    pub next: Option<String>,
+    /// Items on the current page
+    pub items: &'a [ContentContext],
+}
+
+impl<'a> PaginatorContext<'a> {
+    pub fn new(pager: &Pager, items: &'a [ContentContext], style: UrlStyle) -> Self {
+        Self {
+            current_page: pager.number,
+            total_pages: pager.total_pages,
+            total_items: pager.total_items,
+            first: style.link(&pager.first_path),
+            last: style.link(&pager.last_path),
+            prev: pager.prev_path.as_deref().map(|p| style.link(p)),
+            next: pager.next_path.as_deref().map(|p| style.link(p)),
+            items,
         }
     }
 }
@@ -247,23 +507,41 @@ mod tests {
         let config_toc_true = SiteConfig {
             title: "Test".to_string(),
             author: "Test".to_string(),
+            description: None,
             base_url: "https://test.com".to_string(),
+            base_path: None,
             paths: crate::config::PathsConfig::default(),
             nav: crate::config::NavConfig {
                 nested: false,
                 toc: true,
+                ..crate::config::NavConfig::default()
             },
+            taxonomies: Vec::new(),
+            feed: crate::config::FeedConfig::default(),
+            robots: None,
+            redirects: crate::config::RedirectsConfig::default(),
+            urls: crate::config::UrlsConfig::default(),
+            external_links: crate::config::ExternalLinksConfig::default(),
         };
 
         let config_toc_false = SiteConfig {
             title: "Test".to_string(),
             author: "Test".to_string(),
+            description: None,
             base_url: "https://test.com".to_string(),
+            base_path: None,
             paths: crate::config::PathsConfig::default(),
             nav: crate::config::NavConfig {
                 nested: false,
                 toc: false,
+                ..crate::config::NavConfig::default()
             },
+            taxonomies: Vec::new(),
+            feed: crate::config::FeedConfig::default(),
+            robots: None,
+            redirects: crate::config::RedirectsConfig::default(),
+            urls: crate::config::UrlsConfig::default(),
+            external_links: crate::config::ExternalLinksConfig::default(),
         };
 
         // Frontmatter with explicit toc: true
@@ -271,13 +549,31 @@ mod tests {
             title: "Test".to_string(),
             description: None,
             date: None,
+            updated: None,
             tags: vec![],
+            taxonomies: BTreeMap::new(),
             weight: None,
             link_to: None,
             nav_label: None,
             section_type: None,
             template: None,
             toc: Some(true),
+            toc_min_level: None,
+            toc_max_level: None,
+            draft: false,
+            publish_date: None,
+            paginate_by: None,
+            sort_by: None,
+            sort_order: None,
+            feed: None,
+            section_feed: false,
+            sitemap: true,
+            noindex: false,
+            changefreq: None,
+            priority: None,
+            aliases: Vec::new(),
+            slug: None,
+            permalink: None,
         };
 
         // Frontmatter with explicit toc: false
@@ -285,13 +581,31 @@ mod tests {
             title: "Test".to_string(),
             description: None,
             date: None,
+            updated: None,
             tags: vec![],
+            taxonomies: BTreeMap::new(),
             weight: None,
             link_to: None,
             nav_label: None,
             section_type: None,
             template: None,
             toc: Some(false),
+            toc_min_level: None,
+            toc_max_level: None,
+            draft: false,
+            publish_date: None,
+            paginate_by: None,
+            sort_by: None,
+            sort_order: None,
+            feed: None,
+            section_feed: false,
+            sitemap: true,
+            noindex: false,
+            changefreq: None,
+            priority: None,
+            aliases: Vec::new(),
+            slug: None,
+            permalink: None,
         };
 
         // Frontmatter with no toc specified (None)
@@ -299,13 +613,31 @@ mod tests {
             title: "Test".to_string(),
             description: None,
             date: None,
+            updated: None,
             tags: vec![],
+            taxonomies: BTreeMap::new(),
             weight: None,
             link_to: None,
             nav_label: None,
             section_type: None,
             template: None,
             toc: None,
+            toc_min_level: None,
+            toc_max_level: None,
+            draft: false,
+            publish_date: None,
+            paginate_by: None,
+            sort_by: None,
+            sort_order: None,
+            feed: None,
+            section_feed: false,
+            sitemap: true,
+            noindex: false,
+            changefreq: None,
+            priority: None,
+            aliases: Vec::new(),
+            slug: None,
+            permalink: None,
         };
 
         // Explicit true overrides config false
@@ -330,4 +662,26 @@ mod tests {
         let ctx = FrontmatterContext::new(&fm_none, &config_toc_false);
         assert!(!ctx.toc, "toc: None should fall back to config toc: false");
     }
+
+    #[test]
+    fn test_feed_links_follow_formats() {
+        let config: SiteConfig = toml::from_str(
+            r#"
+            title = "Test"
+            author = "Test"
+            base_url = "https://test.com"
+
+            [feed]
+            formats = ["atom", "json"]
+            "#,
+        )
+        .unwrap();
+
+        let links = FeedLinkContext::all(&config, |format| format!("/blog/{}", format.file_name()));
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].path, "/blog/feed.xml");
+        assert_eq!(links[0].mime_type, "application/atom+xml");
+        assert_eq!(links[1].format, FeedFormat::Json);
+        assert_eq!(links[1].path, "/blog/feed.json");
+    }
 }